@@ -0,0 +1,472 @@
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+use crate::config;
+use crate::locale::Locale;
+
+// Read at startup and written on every change
+const BINDINGS_PATH: &str = "config/bindings.ron";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum Action {
+    Shoot,
+    Cancel,
+    AimLeft,
+    AimRight,
+    PowerUp,
+    PowerDown,
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    ZoomIn,
+    ZoomOut,
+    Restart,
+    Mulligan,
+    Pause,
+    MainMenu,
+    MenuUp,
+    MenuDown,
+    MenuConfirm,
+    DebugConsole,
+    DebugOverlay,
+}
+
+impl Action {
+    pub const ALL: [Action; 21] = [
+        Action::Shoot,
+        Action::Cancel,
+        Action::AimLeft,
+        Action::AimRight,
+        Action::PowerUp,
+        Action::PowerDown,
+        Action::PanLeft,
+        Action::PanRight,
+        Action::PanUp,
+        Action::PanDown,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::Restart,
+        Action::Mulligan,
+        Action::Pause,
+        Action::MainMenu,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::MenuConfirm,
+        Action::DebugConsole,
+        Action::DebugOverlay,
+    ];
+
+    // Key of the name shown on the controls page
+    pub fn key(self) -> &'static str {
+        match self {
+            Action::Shoot => "action.shoot",
+            Action::Cancel => "action.cancel",
+            Action::AimLeft => "action.aim_left",
+            Action::AimRight => "action.aim_right",
+            Action::PowerUp => "action.power_up",
+            Action::PowerDown => "action.power_down",
+            Action::PanLeft => "action.pan_left",
+            Action::PanRight => "action.pan_right",
+            Action::PanUp => "action.pan_up",
+            Action::PanDown => "action.pan_down",
+            Action::ZoomIn => "action.zoom_in",
+            Action::ZoomOut => "action.zoom_out",
+            Action::Restart => "action.restart",
+            Action::Mulligan => "action.mulligan",
+            Action::Pause => "action.pause",
+            Action::MainMenu => "action.main_menu",
+            Action::MenuUp => "action.menu_up",
+            Action::MenuDown => "action.menu_down",
+            Action::MenuConfirm => "action.menu_confirm",
+            Action::DebugConsole => "action.debug_console",
+            Action::DebugOverlay => "action.debug_overlay",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+    // Any finger on the screen
+    Touch,
+}
+
+impl Binding {
+    // Keys go by the name printed on them, everything else is looked up by key
+    pub fn label(self, locale: &Locale) -> String {
+        match self {
+            Binding::Key(key) => format!("{:?}", key),
+            Binding::Mouse(MouseButton::Other(number)) => locale.format(
+                "binding.mouse",
+                &[&locale.format("mouse.other", &[&number.to_string()])],
+            ),
+            Binding::Mouse(button) => {
+                locale.format("binding.mouse", &[locale.get(mouse_key(button))])
+            }
+            Binding::Gamepad(GamepadButtonType::Other(number)) => locale.format(
+                "binding.pad",
+                &[&locale.format("pad.other", &[&number.to_string()])],
+            ),
+            Binding::Gamepad(button) => {
+                locale.format("binding.pad", &[locale.get(pad_key(button))])
+            }
+            Binding::Touch => locale.get("binding.touch").to_string(),
+        }
+    }
+
+    // Bindings of the same kind replace each other when rebinding
+    fn same_device(self, other: Binding) -> bool {
+        matches!(
+            (self, other),
+            (Binding::Key(_), Binding::Key(_))
+                | (Binding::Mouse(_), Binding::Mouse(_))
+                | (Binding::Gamepad(_), Binding::Gamepad(_))
+                | (Binding::Touch, Binding::Touch)
+        )
+    }
+}
+
+// Key of the name of a mouse button, other than the numbered ones
+fn mouse_key(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Left => "mouse.left",
+        MouseButton::Right => "mouse.right",
+        MouseButton::Middle => "mouse.middle",
+        MouseButton::Other(_) => "mouse.other",
+    }
+}
+
+// Key of the name of a gamepad button, by where it sits rather than what it's printed
+// with, since that differs between brands
+fn pad_key(button: GamepadButtonType) -> &'static str {
+    match button {
+        GamepadButtonType::South => "pad.south",
+        GamepadButtonType::East => "pad.east",
+        GamepadButtonType::North => "pad.north",
+        GamepadButtonType::West => "pad.west",
+        GamepadButtonType::C => "pad.c",
+        GamepadButtonType::Z => "pad.z",
+        GamepadButtonType::LeftTrigger => "pad.left_bumper",
+        GamepadButtonType::LeftTrigger2 => "pad.left_trigger",
+        GamepadButtonType::RightTrigger => "pad.right_bumper",
+        GamepadButtonType::RightTrigger2 => "pad.right_trigger",
+        GamepadButtonType::Select => "pad.select",
+        GamepadButtonType::Start => "pad.start",
+        GamepadButtonType::Mode => "pad.mode",
+        GamepadButtonType::LeftThumb => "pad.left_stick",
+        GamepadButtonType::RightThumb => "pad.right_stick",
+        GamepadButtonType::DPadUp => "pad.dpad_up",
+        GamepadButtonType::DPadDown => "pad.dpad_down",
+        GamepadButtonType::DPadLeft => "pad.dpad_left",
+        GamepadButtonType::DPadRight => "pad.dpad_right",
+        GamepadButtonType::Other(_) => "pad.other",
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct Bindings(pub BTreeMap<Action, Vec<Binding>>);
+
+impl Default for Bindings {
+    fn default() -> Bindings {
+        use Binding::*;
+
+        let bindings = [
+            (
+                Action::Shoot,
+                vec![
+                    Mouse(MouseButton::Left),
+                    Key(KeyCode::Space),
+                    Gamepad(GamepadButtonType::South),
+                    Touch,
+                ],
+            ),
+            (
+                Action::Cancel,
+                vec![
+                    Mouse(MouseButton::Right),
+                    Key(KeyCode::Back),
+                    Gamepad(GamepadButtonType::East),
+                ],
+            ),
+            (
+                Action::AimLeft,
+                vec![Key(KeyCode::Left), Gamepad(GamepadButtonType::DPadLeft)],
+            ),
+            (
+                Action::AimRight,
+                vec![Key(KeyCode::Right), Gamepad(GamepadButtonType::DPadRight)],
+            ),
+            (
+                Action::PowerUp,
+                vec![Key(KeyCode::Up), Gamepad(GamepadButtonType::DPadUp)],
+            ),
+            (
+                Action::PowerDown,
+                vec![Key(KeyCode::Down), Gamepad(GamepadButtonType::DPadDown)],
+            ),
+            (Action::PanLeft, vec![Key(KeyCode::A)]),
+            (Action::PanRight, vec![Key(KeyCode::D)]),
+            (Action::PanUp, vec![Key(KeyCode::W)]),
+            (Action::PanDown, vec![Key(KeyCode::S)]),
+            (
+                Action::ZoomIn,
+                vec![Key(KeyCode::E), Gamepad(GamepadButtonType::RightTrigger)],
+            ),
+            (
+                Action::ZoomOut,
+                vec![Key(KeyCode::Q), Gamepad(GamepadButtonType::LeftTrigger)],
+            ),
+            (
+                Action::Restart,
+                vec![Key(KeyCode::R), Gamepad(GamepadButtonType::North)],
+            ),
+            (
+                Action::Mulligan,
+                vec![Key(KeyCode::U), Gamepad(GamepadButtonType::West)],
+            ),
+            (
+                Action::Pause,
+                vec![Key(KeyCode::Escape), Gamepad(GamepadButtonType::Start)],
+            ),
+            (
+                Action::MainMenu,
+                vec![Key(KeyCode::M), Gamepad(GamepadButtonType::Select)],
+            ),
+            (
+                Action::MenuUp,
+                vec![Key(KeyCode::Up), Gamepad(GamepadButtonType::DPadUp)],
+            ),
+            (
+                Action::MenuDown,
+                vec![
+                    Key(KeyCode::Down),
+                    Key(KeyCode::Tab),
+                    Gamepad(GamepadButtonType::DPadDown),
+                ],
+            ),
+            (
+                Action::MenuConfirm,
+                vec![
+                    Key(KeyCode::Return),
+                    Key(KeyCode::NumpadEnter),
+                    Key(KeyCode::Space),
+                    Gamepad(GamepadButtonType::South),
+                ],
+            ),
+            (Action::DebugConsole, vec![Key(KeyCode::Grave)]),
+            (Action::DebugOverlay, vec![Key(KeyCode::F3)]),
+        ];
+
+        Bindings(bindings.into_iter().collect())
+    }
+}
+
+impl Bindings {
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.0.entry(action).or_default();
+        bindings.retain(|bound| !bound.same_device(binding));
+        bindings.push(binding);
+    }
+
+    // Missing or broken files fall back to the defaults, and actions the file
+    // doesn't mention keep their default bindings
+    pub fn load() -> Bindings {
+        let mut bindings = Bindings::default();
+        if let Some(saved) = config::load::<Bindings>(BINDINGS_PATH) {
+            bindings.0.extend(saved.0);
+        }
+        bindings
+    }
+
+    pub fn save(&self) {
+        config::save(BINDINGS_PATH, self);
+    }
+}
+
+// Which actions are held this frame, rebuilt from the raw inputs before `Update`
+#[derive(Resource, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    just_released: HashSet<Action>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    // Swallows this frame's press, for UI that sits on top of the course
+    pub fn consume(&mut self, action: Action) {
+        self.just_pressed.remove(&action);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PointerSource {
+    #[default]
+    Mouse,
+    Touch,
+}
+
+// Where the player is pointing, in window coordinates, from the mouse or a finger.
+// A finger that was just lifted keeps its position for that frame so a drag can end there.
+#[derive(Resource, Default)]
+pub struct Pointer {
+    pub position: Option<Vec2>,
+    pub source: PointerSource,
+}
+
+// The action waiting for a new binding, set by the controls screen
+#[derive(Resource, Default)]
+pub struct Rebinding(pub Option<Action>);
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Bindings::load())
+            .init_resource::<ActionState>()
+            .init_resource::<Rebinding>()
+            .init_resource::<Pointer>()
+            .add_systems(
+                PreUpdate,
+                (update_pointer, update_action_state, capture_rebinding)
+                    .chain()
+                    .after(InputSystem),
+            );
+    }
+}
+
+pub fn update_pointer(
+    touches: Res<Touches>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut last_cursor: Local<Option<Vec2>>,
+    mut pointer: ResMut<Pointer>,
+) {
+    if let Some(touch) = touches
+        .iter()
+        .next()
+        .or_else(|| touches.iter_just_released().next())
+    {
+        pointer.position = Some(touch.position());
+        pointer.source = PointerSource::Touch;
+        return;
+    }
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let cursor = window.cursor_position();
+    // Some platforms park the cursor where the last finger was, so only a real
+    // mouse move hands the pointer back to the mouse
+    if pointer.source == PointerSource::Touch && cursor == *last_cursor {
+        return;
+    }
+    *last_cursor = cursor;
+    pointer.position = cursor;
+    pointer.source = PointerSource::Mouse;
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_action_state(
+    bindings: Res<Bindings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    touches: Res<Touches>,
+    rebinding: Res<Rebinding>,
+    mut action_state: ResMut<ActionState>,
+) {
+    let is_pressed = |binding: &Binding| match *binding {
+        Binding::Key(key) => keyboard_input.pressed(key),
+        Binding::Mouse(button) => mouse_input.pressed(button),
+        Binding::Gamepad(button_type) => gamepads
+            .iter()
+            .any(|gamepad| gamepad_input.pressed(GamepadButton::new(gamepad, button_type))),
+        Binding::Touch => touches.iter().next().is_some(),
+    };
+
+    let pressed: HashSet<Action> = Action::ALL
+        .into_iter()
+        .filter(|action| bindings.get(*action).iter().any(is_pressed))
+        .collect();
+
+    // Presses made while rebinding belong to the controls screen, not the game
+    if rebinding.0.is_some() {
+        action_state.just_pressed.clear();
+        action_state.just_released.clear();
+    } else {
+        action_state.just_pressed = pressed.difference(&action_state.pressed).copied().collect();
+        action_state.just_released = action_state.pressed.difference(&pressed).copied().collect();
+    }
+    action_state.pressed = pressed;
+}
+
+pub fn capture_rebinding(
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    touches: Res<Touches>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<Bindings>,
+) {
+    // Skip the frame rebinding started on, or the click that started it gets bound
+    if rebinding.is_changed() {
+        return;
+    }
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+
+    // Escape backs out without changing anything
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        rebinding.0 = None;
+        return;
+    }
+
+    let binding = keyboard_input
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| {
+            mouse_input
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::Mouse(*button))
+        })
+        .or_else(|| {
+            gamepad_input
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::Gamepad(button.button_type))
+        })
+        .or_else(|| touches.any_just_pressed().then_some(Binding::Touch));
+
+    if let Some(binding) = binding {
+        bindings.rebind(action, binding);
+        bindings.save();
+        rebinding.0 = None;
+    }
+}
@@ -0,0 +1,95 @@
+// Checks that a hole can be played without playing it: tries shots with the same
+// physics step as the game and reports the fewest strokes found, a suggested par and
+// where the balls came to rest.
+//
+//     cargo run --release --bin par -- <level file or number>
+//
+// A level file is a RON `LevelLayout`, see `assets/levels/example.ron`. A number
+// checks that built-in hole instead. Exits with failure when the hole can't be solved.
+
+use std::fs;
+use std::process::ExitCode;
+
+use bevy::prelude::*;
+
+use golf_2d_bevy::game::ai::solver::{self, MAX_STROKES};
+use golf_2d_bevy::game::levels::layout::LevelLayout;
+use golf_2d_bevy::game::levels::{Level, LAST_LEVEL};
+use golf_2d_bevy::game::{GameState, SimulationPlugin};
+use golf_2d_bevy::AppState;
+
+const HEATMAP_COLUMNS: usize = 72;
+// Loading a hole takes a few updates
+const MAX_UPDATES: usize = 100;
+
+// Loads the hole the way the game does, then reads it back out of the world
+fn built_in_level(level: i32) -> Option<LevelLayout> {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SimulationPlugin));
+    app.update();
+    app.world.resource_mut::<Level>().0 = level;
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Game);
+    for _ in 0..MAX_UPDATES {
+        app.update();
+        if *app.world.resource::<State<GameState>>().get() == GameState::Flyover {
+            return LevelLayout::from_world(&mut app.world);
+        }
+    }
+    None
+}
+
+fn load(argument: &str) -> Result<LevelLayout, String> {
+    if let Ok(level) = argument.parse::<i32>() {
+        if !(1..=LAST_LEVEL).contains(&level) {
+            return Err(format!("levels go from 1 to {}", LAST_LEVEL));
+        }
+        return built_in_level(level).ok_or_else(|| format!("level {} did not load", level));
+    }
+    let text = fs::read_to_string(argument)
+        .map_err(|error| format!("could not read {}: {}", argument, error))?;
+    LevelLayout::from_ron(&text).map_err(|error| format!("{}: {}", argument, error))
+}
+
+fn main() -> ExitCode {
+    let Some(argument) = std::env::args().nth(1) else {
+        eprintln!("usage: par <level file or number>");
+        return ExitCode::FAILURE;
+    };
+    let layout = match load(&argument) {
+        Ok(layout) => layout,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let solution = solver::solve(&layout);
+    println!(
+        "resting positions of {} shots, {} more went in the water:\n{}",
+        solution.rests.len(),
+        solution.splashed,
+        solver::heatmap(&layout, &solution.rests, HEATMAP_COLUMNS)
+    );
+    let (Some(strokes), Some(par)) = (solution.strokes(), solution.par()) else {
+        println!(
+            "{}: not solvable in {} strokes or fewer",
+            argument, MAX_STROKES
+        );
+        return ExitCode::FAILURE;
+    };
+    println!(
+        "{}: solvable, fewest strokes found {}, suggested par {}",
+        argument, strokes, par
+    );
+    for (stroke, velocity) in solution.shots.iter().enumerate() {
+        println!(
+            "  stroke {}: velocity ({:.1}, {:.1})",
+            stroke + 1,
+            velocity.x,
+            velocity.y
+        );
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,42 @@
+// Relay for online rounds: seats the players and passes their shots around.
+//
+//     cargo run --bin relay -- [address] [players]
+//
+// then start each game with `--connect address`. Defaults to 127.0.0.1:7777 and two
+// players, handy for trying it out on one machine.
+
+use std::net::UdpSocket;
+use std::process::ExitCode;
+
+use golf_2d_bevy::game::players::MAX_PLAYERS;
+use golf_2d_bevy::net::relay;
+use golf_2d_bevy::net::DEFAULT_RELAY;
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| DEFAULT_RELAY.to_string());
+    let players = match args.next().map(|players| players.parse::<usize>()) {
+        None => 2,
+        Some(Ok(players)) if (1..=MAX_PLAYERS).contains(&players) => players,
+        Some(_) => {
+            eprintln!("players must be a number from 1 to {}", MAX_PLAYERS);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let socket = match UdpSocket::bind(&address) {
+        Ok(socket) => socket,
+        Err(error) => {
+            eprintln!("could not listen on {}: {}", address, error);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("relay for {} players on {}", players, address);
+    match relay::serve(socket, players) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("relay stopped: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState};
+use crate::camera::MainCamera;
+use crate::game::levels::LevelBounds;
+use crate::game::players::ActiveBall;
+use crate::game::GameState;
+use crate::RESOLUTION;
+
+// How quickly the camera catches up with its focus, higher is snappier
+const FOLLOW_SHARPNESS: f32 = 4.0;
+// World units per second when panning at zoom 1
+const PAN_SPEED: f32 = 900.0;
+// Zoom change per second while a zoom action is held
+const ZOOM_SPEED: f32 = 1.0;
+// Zoom change per mouse wheel line
+const WHEEL_ZOOM_STEP: f32 = 0.1;
+const MIN_ZOOM: f32 = 0.5;
+// Room left around the hole when the whole of it is shown
+const OVERVIEW_MARGIN: f32 = 1.1;
+const STICK_DEADZONE: f32 = 0.2;
+
+// Seconds the whole hole stays on screen before flying down to the ball
+const OVERVIEW_SECONDS: f32 = 1.2;
+const FLYOVER_SECONDS: f32 = 1.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CameraMode {
+    // Keeps the ball in view
+    #[default]
+    Follow,
+    // The player is looking around the hole
+    Survey,
+    // Scripted tour of a freshly loaded hole
+    Flyover,
+}
+
+#[derive(Resource)]
+pub struct CameraRig {
+    pub mode: CameraMode,
+    // World point the camera moves towards
+    pub focus: Vec2,
+    // Projection scale, 1 shows exactly `RESOLUTION` world units and bigger shows more
+    pub zoom: f32,
+    pub flyover_elapsed: f32,
+}
+
+impl Default for CameraRig {
+    fn default() -> CameraRig {
+        CameraRig {
+            mode: CameraMode::Follow,
+            focus: Vec2::ZERO,
+            zoom: 1.0,
+            flyover_elapsed: 0.0,
+        }
+    }
+}
+
+// Zoom that shows the whole hole, never closer than the default view
+pub fn overview_zoom(bounds: Rect) -> f32 {
+    let size = bounds.size() * OVERVIEW_MARGIN;
+    (size.x / RESOLUTION.x).max(size.y / RESOLUTION.y).max(1.0)
+}
+
+// Keeps the view inside the hole. Axes where the hole is smaller than the view stay centered,
+// so holes that fit on screen look exactly like before.
+pub fn clamp_focus(focus: Vec2, zoom: f32, bounds: Rect) -> Vec2 {
+    let half_view = RESOLUTION * zoom / 2.0;
+    let clamp_axis = |focus: f32, min: f32, max: f32, half_view: f32| {
+        if max - min <= half_view * 2.0 {
+            (min + max) / 2.0
+        } else {
+            focus.clamp(min + half_view, max - half_view)
+        }
+    };
+    Vec2::new(
+        clamp_axis(focus.x, bounds.min.x, bounds.max.x, half_view.x),
+        clamp_axis(focus.y, bounds.min.y, bounds.max.y, half_view.y),
+    )
+}
+
+pub fn start_flyover(
+    level_bounds: Res<LevelBounds>,
+    mut rig: ResMut<CameraRig>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    rig.mode = CameraMode::Flyover;
+    rig.flyover_elapsed = 0.0;
+    rig.focus = level_bounds.0.center();
+    rig.zoom = overview_zoom(level_bounds.0);
+
+    // Cut straight to the overview instead of sliding over from the last hole
+    if let Ok((mut transform, mut projection)) = camera_query.get_single_mut() {
+        transform.translation = rig.focus.extend(transform.translation.z);
+        projection.scale = rig.zoom;
+    }
+}
+
+pub fn run_flyover(
+    action_state: Res<ActionState>,
+    time: Res<Time>,
+    level_bounds: Res<LevelBounds>,
+    ball_query: Query<&Transform, With<ActiveBall>>,
+    mut rig: ResMut<CameraRig>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+) {
+    let Ok(ball_transform) = ball_query.get_single() else {
+        return;
+    };
+    let ball_position = ball_transform.translation.truncate();
+    rig.flyover_elapsed += time.delta_seconds();
+
+    let progress = ((rig.flyover_elapsed - OVERVIEW_SECONDS) / FLYOVER_SECONDS).clamp(0.0, 1.0);
+    // Ease in and out
+    let t = progress * progress * (3.0 - 2.0 * progress);
+    rig.focus = level_bounds.0.center().lerp(ball_position, t);
+    rig.zoom = overview_zoom(level_bounds.0) + (1.0 - overview_zoom(level_bounds.0)) * t;
+
+    let skipped = action_state.just_pressed(Action::Shoot)
+        || action_state.just_pressed(Action::Cancel)
+        || action_state.just_pressed(Action::MenuConfirm);
+    if progress >= 1.0 || skipped {
+        rig.mode = CameraMode::Follow;
+        rig.zoom = 1.0;
+        game_state_next_state.set(GameState::DeadBall);
+    }
+}
+
+pub fn resume_follow(mut rig: ResMut<CameraRig>) {
+    rig.mode = CameraMode::Follow;
+}
+
+pub fn follow_ball(ball_query: Query<&Transform, With<ActiveBall>>, mut rig: ResMut<CameraRig>) {
+    if rig.mode != CameraMode::Follow {
+        return;
+    }
+    if let Ok(ball_transform) = ball_query.get_single() {
+        rig.focus = ball_transform.translation.truncate();
+    }
+}
+
+// Looking around before a swing: pan with the look actions, the right stick or a middle
+// mouse drag, zoom with the zoom actions or the mouse wheel
+#[allow(clippy::too_many_arguments)]
+pub fn survey_hole(
+    action_state: Res<ActionState>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    time: Res<Time>,
+    level_bounds: Res<LevelBounds>,
+    camera_query: Query<&Camera, With<MainCamera>>,
+    mut rig: ResMut<CameraRig>,
+) {
+    let mut pan = Vec2::ZERO;
+    if action_state.pressed(Action::PanLeft) {
+        pan.x -= 1.0;
+    }
+    if action_state.pressed(Action::PanRight) {
+        pan.x += 1.0;
+    }
+    if action_state.pressed(Action::PanUp) {
+        pan.y += 1.0;
+    }
+    if action_state.pressed(Action::PanDown) {
+        pan.y -= 1.0;
+    }
+    for gamepad in gamepads.iter() {
+        let stick = Vec2::new(
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickX))
+                .unwrap_or(0.0),
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY))
+                .unwrap_or(0.0),
+        );
+        if stick.length() > STICK_DEADZONE {
+            pan += stick;
+        }
+    }
+    let mut pan = pan * PAN_SPEED * rig.zoom * time.delta_seconds();
+
+    // Dragging moves the course under the cursor, so the camera goes the other way
+    let mouse_delta: Vec2 = mouse_motion_events.read().map(|motion| motion.delta).sum();
+    if mouse_input.pressed(MouseButton::Middle) {
+        if let Some(viewport_size) = camera_query
+            .get_single()
+            .ok()
+            .and_then(|camera| camera.logical_viewport_size())
+        {
+            let world_per_pixel = RESOLUTION.x * rig.zoom / viewport_size.x;
+            pan += Vec2::new(-mouse_delta.x, mouse_delta.y) * world_per_pixel;
+        }
+    }
+
+    let mut zoom_change = 0.0;
+    if action_state.pressed(Action::ZoomIn) {
+        zoom_change -= ZOOM_SPEED * time.delta_seconds();
+    }
+    if action_state.pressed(Action::ZoomOut) {
+        zoom_change += ZOOM_SPEED * time.delta_seconds();
+    }
+    for wheel in mouse_wheel_events.read() {
+        let lines = match wheel.unit {
+            MouseScrollUnit::Line => wheel.y,
+            MouseScrollUnit::Pixel => wheel.y / 100.0,
+        };
+        zoom_change -= lines * WHEEL_ZOOM_STEP;
+    }
+
+    if pan != Vec2::ZERO {
+        rig.mode = CameraMode::Survey;
+        rig.focus += pan;
+    }
+    if zoom_change != 0.0 {
+        rig.zoom = (rig.zoom * (1.0 + zoom_change)).clamp(MIN_ZOOM, overview_zoom(level_bounds.0));
+    }
+    // Don't let the focus wander off where clamping would hide it
+    if rig.mode == CameraMode::Survey {
+        rig.focus = clamp_focus(rig.focus, rig.zoom, level_bounds.0);
+    }
+}
+
+pub fn move_camera(
+    rig: Res<CameraRig>,
+    time: Res<Time>,
+    level_bounds: Res<LevelBounds>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    let Ok((mut transform, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let (target, zoom) = if rig.mode == CameraMode::Flyover {
+        // Already eased, and the overview may show more than the bounds allow
+        (rig.focus, rig.zoom)
+    } else {
+        let target = clamp_focus(rig.focus, projection.scale, level_bounds.0);
+        let blend = 1.0 - (-FOLLOW_SHARPNESS * time.delta_seconds()).exp();
+        (
+            transform.translation.truncate().lerp(target, blend),
+            projection.scale + (rig.zoom - projection.scale) * blend,
+        )
+    };
+    transform.translation = target.extend(transform.translation.z);
+    projection.scale = zoom;
+}
+
+pub fn reset_camera(
+    mut rig: ResMut<CameraRig>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    *rig = CameraRig::default();
+    if let Ok((mut transform, mut projection)) = camera_query.get_single_mut() {
+        transform.translation = Vec3::new(0.0, 0.0, transform.translation.z);
+        projection.scale = 1.0;
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::core_pipeline::clear_color::ClearColorConfig;
+use bevy::prelude::*;
+use bevy::render::camera::{ScalingMode, Viewport};
+use bevy::render::view::RenderLayers;
+use bevy::ui::UiSystem;
+use bevy::window::PrimaryWindow;
+
+use crate::game::levels::compute_level_bounds;
+use crate::game::{GameState, PauseState};
+use crate::{course_shown, AppState, RESOLUTION};
+
+pub mod follow;
+use follow::*;
+
+// Layer nothing is drawn on, so the UI camera only draws the UI
+const UI_ONLY_LAYER: u8 = 1;
+
+// Draws the course, always showing exactly `RESOLUTION` world units
+#[derive(Component)]
+pub struct MainCamera;
+
+// Draws the UI over the whole window, bars included
+#[derive(Component)]
+pub struct UiCamera;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraRig>()
+            .add_systems(Startup, spawn_cameras)
+            .add_systems(
+                PostUpdate,
+                (
+                    fit_viewport_to_window,
+                    scale_ui_to_window.before(UiSystem::Layout),
+                ),
+            )
+            .add_systems(
+                OnEnter(GameState::Flyover),
+                start_flyover.after(compute_level_bounds),
+            )
+            .add_systems(OnEnter(GameState::DeadBall), resume_follow)
+            .add_systems(OnEnter(GameState::BallMoving), resume_follow)
+            .add_systems(
+                Update,
+                (
+                    run_flyover.run_if(in_state(GameState::Flyover)),
+                    survey_hole.run_if(in_state(GameState::DeadBall)),
+                    follow_ball,
+                    move_camera,
+                )
+                    .chain()
+                    .run_if(course_shown)
+                    .run_if(in_state(PauseState::Running)),
+            )
+            .add_systems(OnExit(AppState::Game), reset_camera)
+            .add_systems(OnExit(AppState::Replay), reset_camera);
+    }
+}
+
+pub fn spawn_cameras(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle {
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::Fixed {
+                    width: RESOLUTION.x,
+                    height: RESOLUTION.y,
+                },
+                ..default()
+            },
+            ..default()
+        },
+        UiCameraConfig { show_ui: false },
+        MainCamera,
+    ));
+
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                ..default()
+            },
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::None,
+            },
+            ..default()
+        },
+        RenderLayers::layer(UI_ONLY_LAYER),
+        UiCamera,
+    ));
+}
+
+// Keeps the course camera at the `RESOLUTION` aspect ratio, centered in the window.
+// The leftover bars keep the background color.
+pub fn fit_viewport_to_window(
+    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut camera_query: Query<&mut Camera, With<MainCamera>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok(mut camera) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let window_size = UVec2::new(window.physical_width(), window.physical_height());
+    let aspect_ratio = RESOLUTION.x / RESOLUTION.y;
+    let size = UVec2::new(
+        window_size
+            .x
+            .min((window_size.y as f32 * aspect_ratio) as u32),
+        window_size
+            .y
+            .min((window_size.x as f32 / aspect_ratio) as u32),
+    );
+    // Minimized windows have nothing to draw into
+    if size.x == 0 || size.y == 0 {
+        return;
+    }
+
+    camera.viewport = Some(Viewport {
+        physical_position: (window_size - size) / 2,
+        physical_size: size,
+        ..default()
+    });
+}
+
+// Menus are laid out for a `RESOLUTION` sized window, this shrinks or grows them
+// to fit the actual one
+pub fn scale_ui_to_window(
+    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let scale = (window.width() / RESOLUTION.x).min(window.height() / RESOLUTION.y);
+    if scale > 0.0 {
+        ui_scale.0 = scale as f64;
+    }
+}
+
+// Converts a window position, like the cursor or a touch, into world coordinates
+pub fn window_to_world(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    position: Vec2,
+) -> Option<Vec2> {
+    let viewport = camera.logical_viewport_rect()?;
+    camera.viewport_to_world_2d(camera_transform, position - viewport.min)
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use std::fs;
+use std::path::Path;
+
+// Player files live next to the game under `config/`. Reading and writing may fail,
+// on the web for one, so every failure just falls back to defaults with a warning.
+
+pub fn load<T: DeserializeOwned>(path: &str) -> Option<T> {
+    let contents = fs::read_to_string(path).ok()?;
+    match ron::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("Ignoring {}: {}", path, error);
+            None
+        }
+    }
+}
+
+pub fn save<T: Serialize>(path: &str, value: &T) {
+    match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => write(path, contents),
+        Err(error) => warn!("Could not serialize {}: {}", path, error),
+    }
+}
+
+// On a single line, for files nobody is meant to edit by hand
+pub fn save_compact<T: Serialize>(path: &str, value: &T) {
+    match ron::ser::to_string(value) {
+        Ok(contents) => write(path, contents),
+        Err(error) => warn!("Could not serialize {}: {}", path, error),
+    }
+}
+
+fn write(path: &str, contents: String) {
+    if let Some(directory) = Path::new(path).parent() {
+        let _ = fs::create_dir_all(directory);
+    }
+    if let Err(error) = fs::write(path, contents) {
+        warn!("Could not save {}: {}", path, error);
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState};
+use crate::debug::DebugLog;
+
+const CONSOLE_FONT_SIZE: f32 = 20.0;
+const CONSOLE_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.7);
+
+#[derive(Component)]
+pub struct DebugConsole;
+
+#[derive(Component)]
+pub struct DebugConsoleText;
+
+// Hidden until toggled, it lives for the whole run so nothing is missed while closed
+pub fn spawn_debug_console(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                background_color: CONSOLE_BACKGROUND_COLOR.into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+            DebugConsole,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: CONSOLE_FONT_SIZE,
+                        ..default()
+                    },
+                ),
+                DebugConsoleText,
+            ));
+        });
+}
+
+pub fn toggle_debug_console(
+    action_state: Res<ActionState>,
+    mut console_query: Query<&mut Visibility, With<DebugConsole>>,
+) {
+    if !action_state.just_pressed(Action::DebugConsole) {
+        return;
+    }
+    for mut visibility in console_query.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+pub fn update_debug_console(
+    debug_log: Res<DebugLog>,
+    mut text_query: Query<&mut Text, With<DebugConsoleText>>,
+) {
+    let lines: Vec<&str> = debug_log.lines.iter().map(String::as_str).collect();
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
@@ -0,0 +1,128 @@
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::log::Level;
+use bevy::prelude::*;
+
+use std::collections::VecDeque;
+
+use crate::game::aiming::Swing;
+use crate::game::components::CollisionEvent;
+use crate::game::{GameState, PauseState};
+use crate::{AppState, GameOver};
+
+mod console;
+use console::*;
+
+mod overlay;
+use overlay::*;
+
+// Lines the console keeps, older ones are dropped
+const DEBUG_LOG_LINES: usize = 12;
+
+// Recent transitions and events, shown by the debug console
+#[derive(Resource, Default)]
+pub struct DebugLog {
+    pub lines: VecDeque<String>,
+}
+
+impl DebugLog {
+    pub fn push(&mut self, time: &Time<Real>, line: String) {
+        if self.lines.len() == DEBUG_LOG_LINES {
+            self.lines.pop_front();
+        }
+        self.lines
+            .push_back(format!("[{:>7.2}] {}", time.elapsed_seconds(), line));
+    }
+}
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(FrameTimeDiagnosticsPlugin)
+            .init_resource::<DebugLog>()
+            .init_resource::<DebugOverlay>()
+            .add_systems(Startup, (spawn_debug_console, spawn_debug_overlay))
+            .add_systems(OnEnter(GameState::LoadingMap), clear_last_collision)
+            .add_systems(
+                Update,
+                (
+                    log_transitions::<AppState>("AppState", Level::INFO)
+                        .run_if(state_changed::<AppState>()),
+                    log_transitions::<GameState>("GameState", Level::DEBUG)
+                        .run_if(state_changed::<GameState>()),
+                    log_transitions::<PauseState>("PauseState", Level::DEBUG)
+                        .run_if(state_changed::<PauseState>()),
+                    log_events,
+                    toggle_debug_console,
+                    update_debug_console.run_if(resource_changed::<DebugLog>()),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    record_last_collision,
+                    draw_debug_overlay,
+                    update_debug_overlay_text,
+                )
+                    .chain(),
+            );
+    }
+}
+
+// Logs every change of `S`, the first run logs the state the app starts in
+#[allow(clippy::type_complexity)]
+pub fn log_transitions<S: States>(
+    name: &'static str,
+    level: Level,
+) -> impl FnMut(Res<State<S>>, Local<Option<S>>, Res<Time<Real>>, ResMut<DebugLog>) {
+    move |state, mut previous, time, mut debug_log| {
+        let current = state.get().clone();
+        match level {
+            Level::INFO => info!(from = ?*previous, to = ?current, "{} transition", name),
+            _ => debug!(from = ?*previous, to = ?current, "{} transition", name),
+        }
+        let line = match previous.as_ref() {
+            Some(from) => format!("{}: {:?} -> {:?}", name, from, current),
+            None => format!("{}: {:?}", name, current),
+        };
+        debug_log.push(&time, line);
+        *previous = Some(current);
+    }
+}
+
+pub fn log_events(
+    mut swing_event_reader: EventReader<Swing>,
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut game_over_event_reader: EventReader<GameOver>,
+    time: Res<Time<Real>>,
+    mut debug_log: ResMut<DebugLog>,
+) {
+    for swing in swing_event_reader.read() {
+        debug!(velocity = ?swing.velocity, "Swing");
+        debug_log.push(&time, format!("Swing {:.0}", swing.velocity));
+    }
+    for collision in collision_event_reader.read() {
+        trace!(normal = ?collision.normal, "Wall hit");
+        debug_log.push(&time, format!("Wall hit {}", collision.normal));
+    }
+    for game_over in game_over_event_reader.read() {
+        for final_score in game_over.scores.iter() {
+            info!(
+                player = final_score.player,
+                final_score = final_score.score,
+                mulligans = final_score.mulligans,
+                "Game over"
+            );
+            debug_log.push(
+                &time,
+                format!(
+                    "Game over, player {} score {}",
+                    final_score.player + 1,
+                    final_score.score
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState};
+use crate::game::components::{Ball, Collider, CollisionEvent, GolfHole, Velocity};
+use crate::game::players::ActiveBall;
+use crate::game::systems::hole_capture_radius;
+use crate::game::GameState;
+
+const OVERLAY_FONT_SIZE: f32 = 20.0;
+const OVERLAY_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.7);
+const COLLIDER_COLOR: Color = Color::FUCHSIA;
+const VELOCITY_COLOR: Color = Color::YELLOW;
+const CAPTURE_RADIUS_COLOR: Color = Color::CYAN;
+const NORMAL_COLOR: Color = Color::RED;
+// The velocity line shows where the ball would be this many seconds from now
+const VELOCITY_LINE_SECONDS: f32 = 0.25;
+const NORMAL_LENGTH: f32 = 40.0;
+
+// Drawn on top of the game while `visible`, toggled with F3
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+    // Where the ball was and which way the wall pushed it on the last hit
+    pub last_collision: Option<(Vec2, Vec2)>,
+}
+
+#[derive(Component)]
+pub struct DebugOverlayPanel;
+
+#[derive(Component)]
+pub struct DebugOverlayText;
+
+pub fn spawn_debug_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    right: Val::Px(5.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                background_color: OVERLAY_BACKGROUND_COLOR.into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+            DebugOverlayPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: OVERLAY_FONT_SIZE,
+                        ..default()
+                    },
+                ),
+                DebugOverlayText,
+            ));
+        });
+}
+
+pub fn toggle_debug_overlay(
+    action_state: Res<ActionState>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut panel_query: Query<&mut Visibility, With<DebugOverlayPanel>>,
+) {
+    if !action_state.just_pressed(Action::DebugOverlay) {
+        return;
+    }
+    overlay.visible = !overlay.visible;
+    for mut visibility in panel_query.iter_mut() {
+        *visibility = if overlay.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+// Kept while hidden too, so the last hit is there as soon as the overlay opens
+pub fn record_last_collision(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    ball_query: Query<&Transform, With<ActiveBall>>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    for collision in collision_event_reader.read() {
+        if let Ok(ball_transform) = ball_query.get_single() {
+            overlay.last_collision =
+                Some((ball_transform.translation.truncate(), collision.normal));
+        }
+    }
+}
+
+// A hit from the previous hole would point at nothing
+pub fn clear_last_collision(mut overlay: ResMut<DebugOverlay>) {
+    overlay.last_collision = None;
+}
+
+pub fn draw_debug_overlay(
+    overlay: Res<DebugOverlay>,
+    game_state: Res<State<GameState>>,
+    collider_query: Query<&Transform, With<Collider>>,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+    hole_query: Query<&Transform, With<GolfHole>>,
+    mut gizmos: Gizmos,
+) {
+    if !overlay.visible {
+        return;
+    }
+    for transform in collider_query.iter() {
+        gizmos.rect_2d(
+            transform.translation.truncate(),
+            0.0,
+            transform.scale.truncate(),
+            COLLIDER_COLOR,
+        );
+    }
+    for transform in hole_query.iter() {
+        gizmos.circle_2d(
+            transform.translation.truncate(),
+            hole_capture_radius(),
+            CAPTURE_RADIUS_COLOR,
+        );
+    }
+    if game_state.get() == &GameState::BallMoving {
+        for (transform, velocity) in ball_query.iter() {
+            let position = transform.translation.truncate();
+            gizmos.line_2d(
+                position,
+                position + velocity.0 * VELOCITY_LINE_SECONDS,
+                VELOCITY_COLOR,
+            );
+        }
+    }
+    if let Some((position, normal)) = overlay.last_collision {
+        gizmos.line_2d(position, position + normal * NORMAL_LENGTH, NORMAL_COLOR);
+    }
+}
+
+pub fn update_debug_overlay_text(
+    overlay: Res<DebugOverlay>,
+    diagnostics: Res<DiagnosticsStore>,
+    game_state: Res<State<GameState>>,
+    mut text_query: Query<&mut Text, With<DebugOverlayText>>,
+) {
+    if !overlay.visible {
+        return;
+    }
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let last_normal = match overlay.last_collision {
+        Some((_, normal)) => format!("{}", normal),
+        None => "-".to_string(),
+    };
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!(
+            "FPS: {:.0}\nGameState: {:?}\nLast normal: {}",
+            fps,
+            game_state.get(),
+            last_normal
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use bevy::prelude::*;
+
+use std::f32::consts::TAU;
+
+use crate::game::aiming::{swing_ball, Swing, MAX_SWING_SPEED};
+use crate::game::components::{Ball, Collider, GolfHole, Water};
+use crate::game::levels::Level;
+use crate::game::physics::{self, Wall};
+use crate::game::players::{ActiveBall, Holed, Player, Players};
+use crate::game::swings_count::Scoreboard;
+use crate::game::systems::{ball_in_hole, ball_in_water, pond_from_transform, wall_from_transform};
+use crate::game::GameState;
+use crate::net::offline;
+use crate::settings::Difficulty;
+use crate::AppState;
+
+pub mod solver;
+
+// Candidate shots of the first pass, every direction at every power
+const SEARCH_ANGLES: usize = 72;
+const SEARCH_POWERS: usize = 12;
+// Finer candidates on each side of the best one of the first pass
+const REFINE_STEPS: usize = 4;
+
+// Pause before the computer swings, so players can follow what it does. Game time,
+// so it waits while the game is paused.
+pub const THINK_SECONDS: f32 = 0.8;
+
+// Where a candidate shot ends up
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlannedShot {
+    pub velocity: Vec2,
+    pub rest: Vec2,
+    pub holed: bool,
+    // Went in a pond and was put back where it was swung from
+    pub in_water: bool,
+    // From where the ball rests to the cup, zero once holed
+    pub distance: f32,
+}
+
+impl PlannedShot {
+    // Closer is better, and among equally good shots the softer one
+    fn beats(&self, other: &PlannedShot) -> bool {
+        (self.distance, self.velocity.length()) < (other.distance, other.velocity.length())
+    }
+}
+
+// Rolls a candidate with `physics::roll` like `step_ball`, `check_ball_in_water` and
+// `check_ball_inside_hole` would. `balls` are every ball still on the course in player
+// order, like `step_ball` sorts them, and `shooter` is the index of the one swung. The
+// others can be knocked away and send the shot somewhere else.
+pub fn try_shot(
+    balls: &[Vec3],
+    shooter: usize,
+    size: Vec2,
+    velocity: Vec2,
+    walls: &[Wall],
+    water: &[Rect],
+    hole: Vec3,
+) -> PlannedShot {
+    let start = balls[shooter];
+    let mut starts = balls.to_vec();
+    let mut states: Vec<physics::BallState> = balls
+        .iter()
+        .enumerate()
+        .map(|(index, position)| physics::BallState {
+            position: *position,
+            velocity: if index == shooter {
+                velocity
+            } else {
+                Vec2::ZERO
+            },
+        })
+        .collect();
+    let mut shooter = shooter;
+    let mut ending = None;
+    physics::roll(&mut states, size, walls, |balls, _| {
+        // Back where it was with a penalty, worse than any shot that stays dry
+        if ball_in_water(balls[shooter].position, water) {
+            ending = Some(PlannedShot {
+                velocity,
+                rest: start.truncate(),
+                holed: false,
+                in_water: true,
+                distance: f32::INFINITY,
+            });
+            return false;
+        }
+        for (ball, start) in balls.iter_mut().zip(&starts) {
+            if ball_in_water(ball.position, water) {
+                ball.position = *start;
+                ball.velocity = Vec2::ZERO;
+            }
+        }
+        if ball_in_hole(balls[shooter].position, hole) {
+            ending = Some(PlannedShot {
+                velocity,
+                rest: hole.truncate(),
+                holed: true,
+                in_water: false,
+                distance: 0.0,
+            });
+            return false;
+        }
+        // Knocked in, it waits in the cup and rolls no more
+        let mut index = 0;
+        while index < balls.len() {
+            if ball_in_hole(balls[index].position, hole) {
+                balls.remove(index);
+                starts.remove(index);
+                if index < shooter {
+                    shooter -= 1;
+                }
+            } else {
+                index += 1;
+            }
+        }
+        true
+    });
+    let rest = states[shooter].position.truncate();
+    ending.unwrap_or_else(|| PlannedShot {
+        velocity,
+        rest,
+        holed: false,
+        in_water: false,
+        distance: rest.distance(hole.truncate()),
+    })
+}
+
+fn velocity_at(angle: f32, power: f32) -> Vec2 {
+    Vec2::from_angle(angle) * power.clamp(0.0, 1.0) * MAX_SWING_SPEED
+}
+
+// Searches every direction and power for the shot that ends closest to the cup, then
+// looks closer around the best one. Same course, same answer.
+pub fn plan_shot(
+    balls: &[Vec3],
+    shooter: usize,
+    size: Vec2,
+    walls: &[Wall],
+    water: &[Rect],
+    hole: Vec3,
+) -> PlannedShot {
+    let angle_step = TAU / SEARCH_ANGLES as f32;
+    let power_step = 1.0 / SEARCH_POWERS as f32;
+
+    let coarse = (0..SEARCH_ANGLES).flat_map(|angle| {
+        (1..=SEARCH_POWERS).map(move |power| (angle as f32 * angle_step, power as f32 * power_step))
+    });
+    let (coarse_shot, coarse_angle, coarse_power) =
+        best_candidate(coarse, balls, shooter, size, walls, water, hole);
+    if coarse_shot.holed {
+        return coarse_shot;
+    }
+
+    let steps = REFINE_STEPS as i32;
+    let refine = 2.0 * REFINE_STEPS as f32;
+    let fine = (-steps..=steps).flat_map(|angle| {
+        (-steps..=steps).map(move |power| {
+            (
+                coarse_angle + angle as f32 * angle_step / refine,
+                coarse_power + power as f32 * power_step / refine,
+            )
+        })
+    });
+    best_candidate(fine, balls, shooter, size, walls, water, hole).0
+}
+
+// The first of the best, with the angle and power it was swung at
+fn best_candidate(
+    candidates: impl Iterator<Item = (f32, f32)>,
+    balls: &[Vec3],
+    shooter: usize,
+    size: Vec2,
+    walls: &[Wall],
+    water: &[Rect],
+    hole: Vec3,
+) -> (PlannedShot, f32, f32) {
+    candidates
+        .map(|(angle, power)| {
+            let velocity = velocity_at(angle, power);
+            let shot = try_shot(balls, shooter, size, velocity, walls, water, hole);
+            (shot, angle, power)
+        })
+        .reduce(|best, candidate| {
+            if candidate.0.beats(&best.0) {
+                candidate
+            } else {
+                best
+            }
+        })
+        .expect("there is always a candidate")
+}
+
+// How far off the computer may swing, as an angle in radians and a share of the power
+pub fn shot_noise(difficulty: Difficulty) -> (f32, f32) {
+    match difficulty {
+        Difficulty::Easy => (0.12, 0.2),
+        Difficulty::Normal => (0.05, 0.08),
+        Difficulty::Hard => (0.01, 0.02),
+    }
+}
+
+// Small deterministic generator (SplitMix64), so the same situation always gets the
+// same miss, in a replay too
+pub struct SplitMix(pub u64);
+
+impl SplitMix {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Evenly spread from -1 to 1
+    pub fn next_signed(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+
+    // Evenly spread from `min` to `max`
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * (self.next_signed() + 1.0) / 2.0
+    }
+
+    // From 0 up to but not including `count`
+    pub fn below(&mut self, count: usize) -> usize {
+        (self.next_u64() % count as u64) as usize
+    }
+}
+
+// Turns and weakens or strengthens a planned shot by up to the difficulty's noise
+pub fn miss(velocity: Vec2, difficulty: Difficulty, seed: u64) -> Vec2 {
+    let (angle_noise, power_noise) = shot_noise(difficulty);
+    let mut rng = SplitMix(seed);
+    let angle = rng.next_signed() * angle_noise;
+    let power = 1.0 + rng.next_signed() * power_noise;
+    let missed = Vec2::from_angle(angle).rotate(velocity) * power;
+    missed.clamp_length_max(MAX_SWING_SPEED)
+}
+
+#[derive(Resource)]
+pub struct ComputerThinking(pub Timer);
+
+impl Default for ComputerThinking {
+    fn default() -> ComputerThinking {
+        ComputerThinking(Timer::from_seconds(THINK_SECONDS, TimerMode::Once))
+    }
+}
+
+// Plays the computer's seat, see `Players::computer`. Its swings are `Swing` events
+// like anyone else's, so they count, get recorded and replay the same way.
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ComputerThinking>()
+            .add_systems(OnEnter(GameState::DeadBall), start_thinking)
+            .add_systems(
+                Update,
+                take_computer_turn
+                    .before(swing_ball)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(GameState::DeadBall))
+                    .run_if(offline),
+            );
+    }
+}
+
+pub fn start_thinking(mut thinking: ResMut<ComputerThinking>) {
+    thinking.0.reset();
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn take_computer_turn(
+    time: Res<Time>,
+    mut thinking: ResMut<ComputerThinking>,
+    players: Res<Players>,
+    level: Res<Level>,
+    scoreboard: Res<Scoreboard>,
+    ball_query: Query<(&Transform, &Player), With<ActiveBall>>,
+    course_ball_query: Query<(&Transform, &Player), (With<Ball>, Without<Holed>)>,
+    collider_query: Query<&Transform, With<Collider>>,
+    water_query: Query<&Transform, With<Water>>,
+    hole_query: Query<&Transform, With<GolfHole>>,
+    mut swing_events: EventWriter<Swing>,
+) {
+    let Ok((ball_transform, player)) = ball_query.get_single() else {
+        return;
+    };
+    let Some(difficulty) = players.computer.filter(|_| players.is_computer(*player)) else {
+        return;
+    };
+    if !thinking.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Ok(hole_transform) = hole_query.get_single() else {
+        return;
+    };
+    let walls = physics::sorted_walls(collider_query.iter().map(wall_from_transform));
+    let water: Vec<Rect> = water_query.iter().map(pond_from_transform).collect();
+    let mut balls: Vec<_> = course_ball_query.iter().collect();
+    balls.sort_by_key(|(_, player)| player.0);
+    let Some(shooter) = balls
+        .iter()
+        .position(|(_, ball_player)| *ball_player == player)
+    else {
+        return;
+    };
+    let balls: Vec<Vec3> = balls
+        .iter()
+        .map(|(transform, _)| transform.translation)
+        .collect();
+
+    let planned = plan_shot(
+        &balls,
+        shooter,
+        ball_transform.scale.truncate(),
+        &walls,
+        &water,
+        hole_transform.translation,
+    );
+    let strokes = scoreboard.card(*player).strokes(level.0) as u64;
+    let seed = (level.0 as u64) << 32 | (player.0 as u64) << 16 | strokes;
+    swing_events.send(Swing {
+        velocity: miss(planned.velocity, difficulty, seed),
+    });
+}
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+
+use std::collections::HashSet;
+use std::f32::consts::TAU;
+
+use crate::game::ai::{try_shot, velocity_at};
+use crate::game::levels::layout::LevelLayout;
+use crate::BALL_SIZE;
+
+// Deepest the search goes, a hole that needs more is not worth playing
+pub const MAX_STROKES: usize = 4;
+// Candidate shots from every resting position, coarser than `plan_shot` since there
+// are many positions to try them from
+const SOLVE_ANGLES: usize = 48;
+const SOLVE_POWERS: usize = 8;
+// Balls resting in the same square of this size count as the same position
+const CELL_SIZE: f32 = 40.0;
+// Most positions played on from after each stroke, the ones closest to the cup
+const MAX_FRONTIER: usize = 64;
+
+// Heat of a heatmap cell, from a single resting ball to the most in any cell
+const HEAT: [char; 8] = ['.', ':', '-', '=', '+', '*', '%', '@'];
+
+#[derive(Clone, Debug, Default)]
+pub struct Solution {
+    // Swings of the shortest way into the cup found, empty when there is none
+    pub shots: Vec<Vec2>,
+    // Where every tried shot that missed the cup and stayed dry stopped
+    pub rests: Vec<Vec2>,
+    // Tried shots that ended in a pond, they never come to rest
+    pub splashed: usize,
+    pub tried: usize,
+}
+
+impl Solution {
+    pub fn strokes(&self) -> Option<usize> {
+        (!self.shots.is_empty()).then_some(self.shots.len())
+    }
+
+    // One over the best found, people rarely find the perfect bank shot
+    pub fn par(&self) -> Option<usize> {
+        self.strokes().map(|strokes| strokes + 1)
+    }
+}
+
+fn candidates() -> Vec<Vec2> {
+    let angle_step = TAU / SOLVE_ANGLES as f32;
+    let power_step = 1.0 / SOLVE_POWERS as f32;
+    (0..SOLVE_ANGLES)
+        .flat_map(|angle| {
+            (1..=SOLVE_POWERS)
+                .map(move |power| velocity_at(angle as f32 * angle_step, power as f32 * power_step))
+        })
+        .collect()
+}
+
+fn cell(position: Vec2) -> (i32, i32) {
+    let cell = (position / CELL_SIZE).floor();
+    (cell.x as i32, cell.y as i32)
+}
+
+// Plays every candidate shot from the tee, then from every new resting position, one
+// stroke at a time, so the first stroke count that holes out is the fewest the search
+// can find. Same layout, same answer.
+pub fn solve(layout: &LevelLayout) -> Solution {
+    let walls = layout.walls();
+    let water = layout.ponds();
+    let size = BALL_SIZE.truncate();
+    let hole = layout.hole.extend(0.0);
+    let candidates = candidates();
+
+    let mut solution = Solution::default();
+    let mut seen = HashSet::from([cell(layout.ball)]);
+    let mut frontier = vec![(layout.ball, Vec::new())];
+    for _ in 0..MAX_STROKES {
+        let mut next: Vec<(Vec2, Vec<Vec2>)> = Vec::new();
+        for (start, path) in &frontier {
+            for velocity in &candidates {
+                let shot = try_shot(
+                    &[start.extend(0.0)],
+                    0,
+                    size,
+                    *velocity,
+                    &walls,
+                    &water,
+                    hole,
+                );
+                solution.tried += 1;
+                let mut path = path.clone();
+                path.push(*velocity);
+                if shot.holed {
+                    if solution.shots.is_empty() {
+                        solution.shots = path;
+                    }
+                } else if shot.in_water {
+                    solution.splashed += 1;
+                } else {
+                    solution.rests.push(shot.rest);
+                    if seen.insert(cell(shot.rest)) {
+                        next.push((shot.rest, path));
+                    }
+                }
+            }
+        }
+        // The rest of the stroke still counts towards the heatmap
+        if !solution.shots.is_empty() || next.is_empty() {
+            break;
+        }
+        next.sort_by(|a, b| {
+            a.0.distance(layout.hole)
+                .total_cmp(&b.0.distance(layout.hole))
+        });
+        next.truncate(MAX_FRONTIER);
+        frontier = next;
+    }
+    solution
+}
+
+// Where the balls stopped, `columns` characters wide with the top of the hole first.
+// Walls are `#`, ponds `~`, the tee `T` and the cup `O`.
+pub fn heatmap(layout: &LevelLayout, rests: &[Vec2], columns: usize) -> String {
+    let bounds = layout.bounds();
+    let size = bounds.size();
+    if columns == 0 || size.x <= 0.0 || size.y <= 0.0 {
+        return String::new();
+    }
+    // Terminal characters are about twice as tall as they are wide
+    let rows = ((columns as f32 * size.y / size.x / 2.0).round() as usize).max(1);
+    let cell_size = size / Vec2::new(columns as f32, rows as f32);
+    let index = |position: Vec2| {
+        let offset = (position - bounds.min) / cell_size;
+        let (column, row) = (offset.x.floor(), offset.y.floor());
+        (column >= 0.0 && row >= 0.0 && column < columns as f32 && row < rows as f32)
+            .then_some((column as usize, row as usize))
+    };
+
+    let mut counts = vec![0; columns * rows];
+    for (column, row) in rests.iter().filter_map(|rest| index(*rest)) {
+        counts[row * columns + column] += 1;
+    }
+    let most = counts.iter().copied().max().unwrap_or(0);
+    let tee = index(layout.ball);
+    let cup = index(layout.hole);
+    let ponds = layout.ponds();
+
+    let mut map = String::new();
+    for row in (0..rows).rev() {
+        for column in 0..columns {
+            let center = bounds.min + cell_size * Vec2::new(column as f32 + 0.5, row as f32 + 0.5);
+            let count = counts[row * columns + column];
+            map.push(if cup == Some((column, row)) {
+                'O'
+            } else if tee == Some((column, row)) {
+                'T'
+            } else if layout
+                .walls
+                .iter()
+                .any(|wall| Rect::from_center_size(wall.center, wall.size).contains(center))
+            {
+                '#'
+            } else if ponds.iter().any(|pond| pond.contains(center)) {
+                '~'
+            } else if count == 0 {
+                ' '
+            } else {
+                HEAT[(count * HEAT.len() - 1) / most]
+            });
+        }
+        map.push('\n');
+    }
+    map
+}
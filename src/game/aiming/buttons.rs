@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState};
+use crate::game::aiming::power_meter::{PowerMeter, PowerMeterPhase};
+use crate::game::aiming::{Aim, AIM_TURN_SPEED, POWER_CHANGE_SPEED};
+use crate::settings::{AimingMode, Settings};
+
+// Turns the aim and changes power with digital buttons, arrow keys and the D-pad by default
+pub fn aim_with_buttons(
+    action_state: Res<ActionState>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    power_meter: Res<PowerMeter>,
+    mut aim: ResMut<Aim>,
+) {
+    // The power meter owns power and, once locked, direction
+    let meter_running = settings.aiming_mode == AimingMode::PowerMeter;
+    let can_turn = !meter_running || power_meter.phase == PowerMeterPhase::Direction;
+
+    let mut turn = 0.0;
+    if action_state.pressed(Action::AimLeft) {
+        turn += 1.0;
+    }
+    if action_state.pressed(Action::AimRight) {
+        turn -= 1.0;
+    }
+    if can_turn && turn != 0.0 {
+        let rotation = Vec2::from_angle(turn * AIM_TURN_SPEED * time.delta_seconds());
+        aim.direction = rotation.rotate(aim.direction);
+    }
+
+    if meter_running {
+        return;
+    }
+
+    let mut power_change = 0.0;
+    if action_state.pressed(Action::PowerUp) {
+        power_change += 1.0;
+    }
+    if action_state.pressed(Action::PowerDown) {
+        power_change -= 1.0;
+    }
+    if power_change != 0.0 {
+        aim.power =
+            (aim.power + power_change * POWER_CHANGE_SPEED * time.delta_seconds()).clamp(0.0, 1.0);
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use crate::game::aiming::power_meter::{PowerMeter, PowerMeterPhase};
+use crate::game::aiming::{Aim, AIM_TURN_SPEED, POWER_CHANGE_SPEED};
+use crate::settings::{AimingMode, Settings};
+
+// Stick deflection below this is ignored
+const STICK_DEADZONE: f32 = 0.2;
+
+fn deadzone(value: f32) -> f32 {
+    if value.abs() < STICK_DEADZONE {
+        0.0
+    } else {
+        value
+    }
+}
+
+// Analog aiming only, gamepad buttons go through the action bindings
+pub fn aim_with_gamepad(
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    button_axes: Res<Axis<GamepadButton>>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    power_meter: Res<PowerMeter>,
+    mut aim: ResMut<Aim>,
+) {
+    for gamepad in gamepads.iter() {
+        let stick_x = deadzone(
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+                .unwrap_or(0.0),
+        );
+        let stick_y = deadzone(
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+                .unwrap_or(0.0),
+        );
+        let right_trigger = button_axes
+            .get(GamepadButton::new(
+                gamepad,
+                GamepadButtonType::RightTrigger2,
+            ))
+            .unwrap_or(0.0);
+        let left_trigger = button_axes
+            .get(GamepadButton::new(gamepad, GamepadButtonType::LeftTrigger2))
+            .unwrap_or(0.0);
+
+        // The power meter owns power and, once locked, direction
+        let meter_running = settings.aiming_mode == AimingMode::PowerMeter;
+        let can_turn = !meter_running || power_meter.phase == PowerMeterPhase::Direction;
+
+        if can_turn && stick_x != 0.0 {
+            // Pushing right turns clockwise
+            let turn = Vec2::from_angle(-stick_x * AIM_TURN_SPEED * time.delta_seconds());
+            aim.direction = turn.rotate(aim.direction);
+        }
+
+        if !meter_running {
+            let power_change = right_trigger - left_trigger + stick_y;
+            aim.power = (aim.power + power_change * POWER_CHANGE_SPEED * time.delta_seconds())
+                .clamp(0.0, 1.0);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use bevy::prelude::*;
+
+use std::f32::consts::PI;
+
+use crate::actions::{Action, ActionState, Pointer, PointerSource};
+use crate::camera::{window_to_world, MainCamera};
+use crate::game::components::*;
+use crate::game::levels::Level;
+use crate::game::players::{human_turn, ActiveBall, Holed, Player};
+use crate::game::retry::{LastSwing, SwingStart};
+use crate::game::swings_count::Scoreboard;
+use crate::game::{GameState, PauseState};
+use crate::net::local_turn;
+use crate::settings::{AimingMode, Settings};
+use crate::AppState;
+
+pub mod power_meter;
+use power_meter::*;
+
+pub mod gamepad;
+use gamepad::*;
+
+pub mod buttons;
+use buttons::*;
+
+pub mod preview;
+use preview::*;
+
+// Longest the aim arrow can get, in world units
+pub const VELOCITY_VECTOR_SIZE: f32 = 275.;
+const VELOCITY_FACTOR: f32 = 3.5;
+// Speed of a full power swing
+pub const MAX_SWING_SPEED: f32 = VELOCITY_VECTOR_SIZE * VELOCITY_FACTOR;
+
+// Radians per second when turning the aim with keys or a stick
+pub const AIM_TURN_SPEED: f32 = 2.5;
+// Power gained or lost per second while a power key or trigger is held
+pub const POWER_CHANGE_SPEED: f32 = 0.8;
+
+// How close to the ball a press has to land to grab it in slingshot mode
+const SLINGSHOT_GRAB_RADIUS: f32 = 50.0;
+// Releases weaker than this are treated as a cancelled drag
+const SLINGSHOT_MIN_POWER: f32 = 0.05;
+
+// Direction is a unit vector and power goes from 0 to 1
+#[derive(Resource)]
+pub struct Aim {
+    pub direction: Vec2,
+    pub power: f32,
+}
+
+impl Default for Aim {
+    fn default() -> Aim {
+        Aim {
+            direction: Vec2::Y,
+            power: 0.0,
+        }
+    }
+}
+
+impl Aim {
+    // Sets direction and power from an arrow drawn in world units
+    pub fn set_from_vector(&mut self, vector: Vec2) {
+        self.direction = vector.normalize_or_zero();
+        self.power = (vector.length() / VELOCITY_VECTOR_SIZE).min(1.0);
+    }
+
+    pub fn arrow(&self) -> Vec2 {
+        self.direction * self.power * VELOCITY_VECTOR_SIZE
+    }
+
+    pub fn velocity(&self) -> Vec2 {
+        self.arrow() * VELOCITY_FACTOR
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Slingshot {
+    pub dragging: bool,
+}
+
+// Last cursor position seen in pointer mode, so other inputs can aim while the mouse rests
+#[derive(Resource, Default)]
+pub struct PointerCursor(pub Option<Vec2>);
+
+// Sent by any input method once a shot is decided
+#[derive(Event)]
+pub struct Swing {
+    pub velocity: Vec2,
+}
+
+pub struct AimingPlugin;
+
+impl Plugin for AimingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Aim>()
+            .init_resource::<Slingshot>()
+            .init_resource::<PowerMeter>()
+            .init_resource::<PointerCursor>()
+            .add_systems(
+                OnEnter(GameState::DeadBall),
+                (
+                    reset_aim,
+                    reset_power_meter,
+                    spawn_power_meter.run_if(aiming_mode_is(AimingMode::PowerMeter)),
+                )
+                    .run_if(in_state(AppState::Game)),
+            )
+            .add_systems(OnExit(GameState::DeadBall), despawn_power_meter)
+            .add_systems(
+                Update,
+                (
+                    aim_with_pointer
+                        .run_if(aiming_mode_is(AimingMode::Pointer).and_then(pointer_is_mouse)),
+                    aim_with_slingshot.run_if(uses_slingshot),
+                    aim_with_power_meter.run_if(aiming_mode_is(AimingMode::PowerMeter)),
+                    aim_with_gamepad,
+                    aim_with_buttons,
+                    shoot_with_action,
+                    update_velocity_vector,
+                    draw_trajectory_preview,
+                    update_power_meter.run_if(aiming_mode_is(AimingMode::PowerMeter)),
+                )
+                    .chain()
+                    .before(swing_ball)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(GameState::DeadBall))
+                    .run_if(in_state(PauseState::Running))
+                    .run_if(local_turn)
+                    .run_if(human_turn),
+            );
+    }
+}
+
+pub fn aiming_mode_is(mode: AimingMode) -> impl Fn(Res<Settings>) -> bool {
+    move |settings: Res<Settings>| settings.aiming_mode == mode
+}
+
+pub fn pointer_is_mouse(pointer: Res<Pointer>) -> bool {
+    pointer.source == PointerSource::Mouse
+}
+
+// Fingers always drag from the ball, except for the power meter which only needs taps
+pub fn uses_slingshot(settings: Res<Settings>, pointer: Res<Pointer>) -> bool {
+    match settings.aiming_mode {
+        AimingMode::Slingshot => true,
+        AimingMode::Pointer => pointer.source == PointerSource::Touch,
+        AimingMode::PowerMeter => false,
+    }
+}
+
+// Where the pointer is in the world, if anywhere
+pub fn pointer_to_world(
+    pointer: &Pointer,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) -> Option<Vec2> {
+    let (camera, camera_transform) = camera_query.get_single().ok()?;
+    window_to_world(camera, camera_transform, pointer.position?)
+}
+
+pub fn reset_aim(
+    mut aim: ResMut<Aim>,
+    mut slingshot: ResMut<Slingshot>,
+    mut pointer_cursor: ResMut<PointerCursor>,
+) {
+    *aim = Aim::default();
+    slingshot.dragging = false;
+    pointer_cursor.0 = None;
+}
+
+pub fn aim_with_pointer(
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    ball_query: Query<&Transform, With<ActiveBall>>,
+    pointer: Res<Pointer>,
+    mut aim: ResMut<Aim>,
+    mut pointer_cursor: ResMut<PointerCursor>,
+) {
+    let Some(cursor_position) = pointer_to_world(&pointer, &camera_query) else {
+        return;
+    };
+    let Ok(ball_transform) = ball_query.get_single() else {
+        return;
+    };
+    let ball_position = ball_transform.translation.truncate();
+
+    if pointer_cursor.0 != Some(cursor_position) {
+        pointer_cursor.0 = Some(cursor_position);
+        aim.set_from_vector(cursor_position - ball_position);
+    }
+}
+
+pub fn aim_with_slingshot(
+    action_state: Res<ActionState>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    ball_query: Query<&Transform, With<ActiveBall>>,
+    pointer: Res<Pointer>,
+    mut aim: ResMut<Aim>,
+    mut slingshot: ResMut<Slingshot>,
+    mut swing_events: EventWriter<Swing>,
+) {
+    let Some(cursor_position) = pointer_to_world(&pointer, &camera_query) else {
+        return;
+    };
+    let Ok(ball_transform) = ball_query.get_single() else {
+        return;
+    };
+    let ball_position = ball_transform.translation.truncate();
+
+    if !slingshot.dragging {
+        if action_state.just_pressed(Action::Shoot)
+            && cursor_position.distance(ball_position) <= SLINGSHOT_GRAB_RADIUS
+        {
+            slingshot.dragging = true;
+        }
+        return;
+    }
+
+    // Pulling back from the ball aims the other way
+    aim.set_from_vector(ball_position - cursor_position);
+
+    if action_state.just_pressed(Action::Cancel) {
+        *aim = Aim::default();
+        slingshot.dragging = false;
+    } else if action_state.just_released(Action::Shoot) {
+        if aim.power >= SLINGSHOT_MIN_POWER {
+            swing_events.send(Swing {
+                velocity: aim.velocity(),
+            });
+        }
+        *aim = Aim::default();
+        slingshot.dragging = false;
+    }
+}
+
+// Shoots whatever the aim currently is, the power meter and a slingshot drag
+// decide on their own when to shoot. A tap is never a shot on its own, fingers
+// have to drag from the ball.
+pub fn shoot_with_action(
+    action_state: Res<ActionState>,
+    settings: Res<Settings>,
+    pointer: Res<Pointer>,
+    slingshot: Res<Slingshot>,
+    aim: Res<Aim>,
+    mut swing_events: EventWriter<Swing>,
+) {
+    if settings.aiming_mode == AimingMode::PowerMeter
+        || slingshot.dragging
+        || pointer.source == PointerSource::Touch
+    {
+        return;
+    }
+    if action_state.just_pressed(Action::Shoot) && aim.power > 0.0 {
+        swing_events.send(Swing {
+            velocity: aim.velocity(),
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_velocity_vector(
+    aim: Res<Aim>,
+    ball_query: Query<&Transform, (With<Ball>, With<ActiveBall>)>,
+    mut vector_quad_query: Query<
+        (&mut Transform, &mut Visibility),
+        (
+            With<VelocityVectorQuad>,
+            Without<Ball>,
+            Without<VelocityVectorTriangle>,
+        ),
+    >,
+    mut vector_triangle_query: Query<
+        (&mut Transform, &mut Visibility),
+        (
+            With<VelocityVectorTriangle>,
+            Without<Ball>,
+            Without<VelocityVectorQuad>,
+        ),
+    >,
+) {
+    let (
+        Ok((mut vector_quad, mut quad_visibility)),
+        Ok((mut vector_triangle, mut triangle_visibility)),
+    ) = (
+        vector_quad_query.get_single_mut(),
+        vector_triangle_query.get_single_mut(),
+    )
+    else {
+        return;
+    };
+    let Ok(ball_transform) = ball_query.get_single() else {
+        return;
+    };
+    let ball_position = ball_transform.translation;
+    let ball_velocity_vector = aim.arrow();
+
+    let visibility = if aim.power > 0.0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    *quad_visibility = visibility;
+    *triangle_visibility = visibility;
+
+    // Rotate vector arrow
+    vector_quad.translation.x = ball_velocity_vector.x / 2.0 + ball_position.x;
+    vector_quad.translation.y = ball_velocity_vector.y / 2.0 + ball_position.y;
+    vector_quad.scale.y = ball_velocity_vector.length();
+
+    vector_triangle.translation.x = ball_velocity_vector.x + ball_position.x;
+    vector_triangle.translation.y = ball_velocity_vector.y + ball_position.y;
+
+    let vector_orient = PI / 2.0;
+    let mut angle_to_target = ball_velocity_vector.y.atan2(ball_velocity_vector.x);
+    if angle_to_target < 0. {
+        angle_to_target += 2.0 * PI;
+    }
+    let angle_to_rotate = angle_to_target - vector_orient;
+    vector_quad.rotation = Quat::from_rotation_z(angle_to_rotate);
+    vector_triangle.rotation = Quat::from_rotation_z(angle_to_rotate);
+}
+
+#[allow(clippy::type_complexity)]
+pub fn swing_ball(
+    mut swing_events: EventReader<Swing>,
+    mut ball_query: Query<(Entity, &mut Velocity, &Player), With<ActiveBall>>,
+    positions_query: Query<(Entity, &Transform), (With<Ball>, Without<Holed>)>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut last_swing: ResMut<LastSwing>,
+    level: Res<Level>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(swing) = swing_events.read().last() else {
+        return;
+    };
+    let Ok((entity, mut ball_velocity, player)) = ball_query.get_single_mut() else {
+        return;
+    };
+
+    let card = scoreboard.card_mut(*player);
+    card.score += 1;
+    card.hole_mut(level.0).strokes += 1;
+    last_swing.0 = Some(SwingStart {
+        ball: entity,
+        positions: positions_query
+            .iter()
+            .map(|(entity, transform)| (entity, transform.translation))
+            .collect(),
+    });
+    *ball_velocity = Velocity(swing.velocity);
+    game_state_next_state.set(GameState::BallMoving);
+}
@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState, Pointer};
+use crate::camera::MainCamera;
+use crate::game::aiming::{pointer_to_world, Aim, PointerCursor, Swing};
+use crate::game::players::ActiveBall;
+use crate::settings::Settings;
+
+// Seconds for the power bar to go from empty to full and back
+const POWER_METER_PERIOD: f32 = 1.6;
+// The accuracy marker moves faster to make the third click harder
+const ACCURACY_METER_PERIOD: f32 = 0.9;
+// Largest angle, in radians, a badly timed accuracy click can add
+const MAX_ACCURACY_ERROR: f32 = 0.2;
+
+const METER_WIDTH: f32 = 400.0;
+const METER_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.5);
+const ACCURACY_MARKER_COLOR: Color = Color::WHITE;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PowerMeterPhase {
+    #[default]
+    Direction,
+    Power,
+    Accuracy,
+}
+
+#[derive(Resource, Default)]
+pub struct PowerMeter {
+    pub phase: PowerMeterPhase,
+    pub elapsed: f32,
+    // Goes from -1 to 1, with 0 being a perfectly straight shot
+    pub accuracy: f32,
+}
+
+#[derive(Component)]
+pub struct PowerMeterUi;
+
+#[derive(Component)]
+pub struct PowerMeterFill;
+
+#[derive(Component)]
+pub struct AccuracyMeter;
+
+#[derive(Component)]
+pub struct AccuracyMarker;
+
+// Goes 0 -> 1 -> 0 once per period
+fn oscillate(elapsed: f32, period: f32) -> f32 {
+    1.0 - (2.0 * (elapsed / period).fract() - 1.0).abs()
+}
+
+pub fn reset_power_meter(mut power_meter: ResMut<PowerMeter>) {
+    *power_meter = PowerMeter::default();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn aim_with_power_meter(
+    action_state: Res<ActionState>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    ball_query: Query<&Transform, With<ActiveBall>>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    pointer: Res<Pointer>,
+    mut aim: ResMut<Aim>,
+    mut pointer_cursor: ResMut<PointerCursor>,
+    mut power_meter: ResMut<PowerMeter>,
+    mut swing_events: EventWriter<Swing>,
+) {
+    if action_state.just_pressed(Action::Cancel) {
+        *power_meter = PowerMeter::default();
+    }
+
+    let clicked = action_state.just_pressed(Action::Shoot);
+    power_meter.elapsed += time.delta_seconds();
+
+    match power_meter.phase {
+        PowerMeterPhase::Direction => {
+            let cursor_position = pointer_to_world(&pointer, &camera_query);
+            let Ok(ball_transform) = ball_query.get_single() else {
+                return;
+            };
+            let ball_position = ball_transform.translation.truncate();
+            // Only follow the cursor when it moves, so a gamepad can turn the arrow too
+            if let Some(cursor_position) = cursor_position.filter(|p| pointer_cursor.0 != Some(*p))
+            {
+                pointer_cursor.0 = Some(cursor_position);
+                let direction = (cursor_position - ball_position).normalize_or_zero();
+                if direction != Vec2::ZERO {
+                    aim.direction = direction;
+                }
+            }
+            // Full length arrow, only the direction matters at this point
+            aim.power = 1.0;
+
+            if clicked {
+                power_meter.phase = PowerMeterPhase::Power;
+                power_meter.elapsed = 0.0;
+                aim.power = 0.0;
+            }
+        }
+        PowerMeterPhase::Power => {
+            aim.power = oscillate(power_meter.elapsed, POWER_METER_PERIOD);
+
+            if clicked {
+                if settings.accuracy_click {
+                    power_meter.phase = PowerMeterPhase::Accuracy;
+                    power_meter.elapsed = 0.0;
+                } else {
+                    swing_events.send(Swing {
+                        velocity: aim.velocity(),
+                    });
+                }
+            }
+        }
+        PowerMeterPhase::Accuracy => {
+            power_meter.accuracy =
+                2.0 * oscillate(power_meter.elapsed, ACCURACY_METER_PERIOD) - 1.0;
+
+            if clicked {
+                let error = Vec2::from_angle(power_meter.accuracy * MAX_ACCURACY_ERROR);
+                aim.direction = error.rotate(aim.direction);
+                swing_events.send(Swing {
+                    velocity: aim.velocity(),
+                });
+            }
+        }
+    }
+}
+
+pub fn spawn_power_meter(mut commands: Commands, settings: Res<Settings>) {
+    let palette = settings.palette();
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(40.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                ..default()
+            },
+            PowerMeterUi,
+        ))
+        .with_children(|parent| {
+            // Power bar
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(METER_WIDTH),
+                        height: Val::Px(24.0),
+                        ..default()
+                    },
+                    background_color: METER_BACKGROUND_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: palette.power_fill.into(),
+                            ..default()
+                        },
+                        PowerMeterFill,
+                    ));
+                });
+            // Accuracy bar
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(METER_WIDTH),
+                            height: Val::Px(12.0),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        background_color: METER_BACKGROUND_COLOR.into(),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    AccuracyMeter,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(2.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: palette.accuracy_center.into(),
+                        ..default()
+                    });
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                width: Val::Px(4.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: ACCURACY_MARKER_COLOR.into(),
+                            ..default()
+                        },
+                        AccuracyMarker,
+                    ));
+                });
+        });
+}
+
+pub fn despawn_power_meter(
+    mut commands: Commands,
+    power_meter_query: Query<Entity, With<PowerMeterUi>>,
+) {
+    for entity in power_meter_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_power_meter(
+    aim: Res<Aim>,
+    power_meter: Res<PowerMeter>,
+    mut fill_query: Query<&mut Style, (With<PowerMeterFill>, Without<AccuracyMarker>)>,
+    mut marker_query: Query<&mut Style, (With<AccuracyMarker>, Without<PowerMeterFill>)>,
+    mut accuracy_meter_query: Query<&mut Visibility, With<AccuracyMeter>>,
+) {
+    let power = match power_meter.phase {
+        PowerMeterPhase::Direction => 0.0,
+        _ => aim.power,
+    };
+    for mut style in fill_query.iter_mut() {
+        style.width = Val::Percent(power * 100.0);
+    }
+    for mut style in marker_query.iter_mut() {
+        style.left = Val::Percent((power_meter.accuracy + 1.0) * 50.0);
+    }
+    for mut visibility in accuracy_meter_query.iter_mut() {
+        *visibility = if power_meter.phase == PowerMeterPhase::Accuracy {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::game::aiming::Aim;
+use crate::game::components::*;
+use crate::game::physics;
+use crate::game::players::ActiveBall;
+use crate::game::systems::wall_from_transform;
+use crate::settings::Settings;
+
+const PREVIEW_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.6);
+
+// Draws where the current aim would send the ball, up to the difficulty's bounce limit
+pub fn draw_trajectory_preview(
+    aim: Res<Aim>,
+    settings: Res<Settings>,
+    ball_query: Query<&Transform, With<ActiveBall>>,
+    collider_query: Query<&Transform, With<Collider>>,
+    mut gizmos: Gizmos,
+) {
+    let Some(max_bounces) = settings.difficulty.preview_bounces() else {
+        return;
+    };
+    if aim.power <= 0.0 {
+        return;
+    }
+    let Ok(ball_transform) = ball_query.get_single() else {
+        return;
+    };
+    let walls = physics::sorted_walls(collider_query.iter().map(wall_from_transform));
+
+    let path = physics::simulate_shot(
+        ball_transform.translation,
+        ball_transform.scale.truncate(),
+        aim.velocity(),
+        &walls,
+        max_bounces,
+    );
+    gizmos.linestrip_2d(path, PREVIEW_COLOR);
+}
@@ -1,12 +1,4 @@
-use bevy::{
-    prelude::*,
-    sprite::collide_aabb::{collide, Collision},
-    sprite::MaterialMesh2dBundle,
-    transform::commands,
-};
-
-#[derive(Resource)]
-pub struct BackgroundColor(Color);
+use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct Ball;
@@ -29,5 +21,12 @@ pub struct Velocity(pub Vec2);
 #[derive(Component)]
 pub struct Collider;
 
+// A pond, a ball that rolls into it goes back to where it was swung from
+#[derive(Component)]
+pub struct Water;
+
 #[derive(Event, Default)]
-pub struct CollisionEvent;
+pub struct CollisionEvent {
+    // Side of the wall that was hit, pointing away from it
+    pub normal: Vec2,
+}
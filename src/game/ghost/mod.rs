@@ -0,0 +1,286 @@
+use bevy::prelude::*;
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+use crate::config;
+use crate::game::aiming::{swing_ball, Swing};
+use crate::game::components::{Collider, GolfHole};
+use crate::game::levels::{classic_course, Level};
+use crate::game::physics;
+use crate::game::players::{single_player, ActiveBall, Player};
+use crate::game::replay::{RoundTick, Shot};
+use crate::game::swings_count::Scoreboard;
+use crate::game::systems::{ball_in_hole, set_load_map_state, wall_from_transform};
+use crate::game::GameState;
+use crate::settings::Settings;
+use crate::{AppState, BALL_SIZE};
+
+// Read at startup and written every time a hole is beaten
+const PERSONAL_BESTS_PATH: &str = "config/personal_bests.ron";
+
+const GHOST_ALPHA: f32 = 0.35;
+// Under the ball, which sits at 2
+const GHOST_Z: f32 = 1.0;
+
+// Shots of the best attempt at each hole, fewest strokes wins
+#[derive(Resource, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonalBests {
+    pub holes: BTreeMap<i32, Vec<Shot>>,
+}
+
+impl PersonalBests {
+    pub fn load() -> PersonalBests {
+        config::load(PERSONAL_BESTS_PATH).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        config::save_compact(PERSONAL_BESTS_PATH, self);
+    }
+
+    // Keeps the attempt if it beats the best one, ties keep the older attempt
+    pub fn record(&mut self, hole: i32, attempt: &[Shot]) {
+        let tied_or_worse = self
+            .holes
+            .get(&hole)
+            .is_some_and(|best| attempt.len() >= best.len());
+        if !tied_or_worse && !attempt.is_empty() {
+            self.holes.insert(hole, attempt.to_vec());
+        }
+    }
+}
+
+// The shots that count on the current hole, with mulligans and restarts taken back
+#[derive(Resource, Default)]
+pub struct HoleAttempt(pub Vec<Shot>);
+
+// Keeps the best attempt at every hole, so `GhostPlugin` has something to replay.
+// Only solo rounds of the built-in holes count, in hot seat the course is shared and
+// an endless course has new holes every run.
+pub struct PersonalBestPlugin;
+
+impl Plugin for PersonalBestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PersonalBests>()
+            .init_resource::<HoleAttempt>()
+            .add_systems(
+                Update,
+                track_hole_attempt
+                    .before(swing_ball)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(single_player)
+                    .run_if(classic_course)
+                    .run_if(in_state(GameState::DeadBall)),
+            )
+            .add_systems(
+                OnEnter(GameState::UnloadingMap),
+                keep_personal_best
+                    .before(set_load_map_state)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(single_player)
+                    .run_if(classic_course),
+            );
+    }
+}
+
+// Strokes already on the card are the shots that still count, anything after them
+// was taken back by a mulligan or a restart
+pub fn track_hole_attempt(
+    mut swing_events: EventReader<Swing>,
+    ball_query: Query<&Transform, With<ActiveBall>>,
+    level: Res<Level>,
+    scoreboard: Res<Scoreboard>,
+    round_tick: Res<RoundTick>,
+    mut attempt: ResMut<HoleAttempt>,
+) {
+    let Some(swing) = swing_events.read().last() else {
+        return;
+    };
+    let Ok(ball_transform) = ball_query.get_single() else {
+        return;
+    };
+    let strokes = scoreboard.card(Player(0)).strokes(level.0);
+    attempt.0.truncate(strokes);
+    attempt.0.push(Shot {
+        player: 0,
+        hole: level.0,
+        start: ball_transform.translation.truncate(),
+        velocity: swing.velocity,
+        tick: round_tick.0,
+        others: Vec::new(),
+    });
+}
+
+pub fn keep_personal_best(
+    level: Res<Level>,
+    attempt: Res<HoleAttempt>,
+    mut personal_bests: ResMut<PersonalBests>,
+) {
+    personal_bests.record(level.0, &attempt.0);
+}
+
+// The best attempt at the hole, rolling along with the player's strokes
+#[derive(Component, Default)]
+pub struct Ghost {
+    pub velocity: Vec2,
+    pub sunk: bool,
+}
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PersonalBests::load())
+            .add_systems(
+                Update,
+                save_personal_bests.run_if(resource_changed::<PersonalBests>()),
+            )
+            .add_systems(
+                OnEnter(GameState::Flyover),
+                spawn_ghost
+                    .run_if(in_state(AppState::Game))
+                    .run_if(single_player)
+                    .run_if(classic_course),
+            )
+            .add_systems(
+                Update,
+                launch_ghost
+                    .before(swing_ball)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(GameState::DeadBall)),
+            )
+            .add_systems(FixedUpdate, step_ghost.run_if(in_state(AppState::Game)))
+            .add_systems(Update, update_ghost_looks.run_if(in_state(AppState::Game)))
+            .add_systems(OnEnter(GameState::LoadingMap), despawn_ghost)
+            .add_systems(OnExit(AppState::Game), despawn_ghost);
+    }
+}
+
+pub fn save_personal_bests(personal_bests: Res<PersonalBests>) {
+    // The first change is the file being loaded, nothing to write back
+    if !personal_bests.is_added() {
+        personal_bests.save();
+    }
+}
+
+fn ghost_color(settings: &Settings) -> Color {
+    settings.palette().ball.with_a(GHOST_ALPHA)
+}
+
+// Waits at the start of the best attempt until the first swing
+pub fn spawn_ghost(
+    mut commands: Commands,
+    level: Res<Level>,
+    personal_bests: Res<PersonalBests>,
+    settings: Res<Settings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let Some(first_shot) = personal_bests
+        .holes
+        .get(&level.0)
+        .and_then(|best| best.first())
+    else {
+        return;
+    };
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(shape::Circle::default().into())),
+            material: materials.add(ColorMaterial::from(ghost_color(&settings))),
+            transform: Transform::from_translation(first_shot.start.extend(GHOST_Z))
+                .with_scale(BALL_SIZE),
+            ..default()
+        },
+        Ghost::default(),
+    ));
+}
+
+// Every stroke sends the ghost off on the same stroke of the best attempt
+pub fn launch_ghost(
+    mut swing_events: EventReader<Swing>,
+    level: Res<Level>,
+    scoreboard: Res<Scoreboard>,
+    personal_bests: Res<PersonalBests>,
+    mut ghost_query: Query<(&mut Transform, &mut Ghost)>,
+) {
+    if swing_events.read().last().is_none() {
+        return;
+    }
+    let Ok((mut ghost_transform, mut ghost)) = ghost_query.get_single_mut() else {
+        return;
+    };
+    let strokes = scoreboard.card(Player(0)).strokes(level.0);
+    let shot = personal_bests
+        .holes
+        .get(&level.0)
+        .and_then(|best| best.get(strokes));
+
+    match shot {
+        Some(shot) => {
+            ghost_transform.translation = shot.start.extend(GHOST_Z);
+            ghost.velocity = shot.velocity;
+            ghost.sunk = false;
+        }
+        // The best attempt was already in the hole by now
+        None => {
+            ghost.velocity = Vec2::ZERO;
+            ghost.sunk = true;
+        }
+    }
+}
+
+// Same step as the ball, so the ghost rolls exactly like the attempt it replays
+pub fn step_ghost(
+    mut ghost_query: Query<(&mut Transform, &mut Ghost)>,
+    collider_query: Query<&Transform, (With<Collider>, Without<Ghost>)>,
+    hole_query: Query<&Transform, (With<GolfHole>, Without<Ghost>)>,
+) {
+    let Ok((mut ghost_transform, mut ghost)) = ghost_query.get_single_mut() else {
+        return;
+    };
+    if ghost.sunk || ghost.velocity == Vec2::ZERO {
+        return;
+    }
+    let walls = physics::sorted_walls(collider_query.iter().map(wall_from_transform));
+
+    let mut ball = physics::BallState {
+        position: ghost_transform.translation,
+        velocity: ghost.velocity,
+    };
+    physics::step(&mut ball, ghost_transform.scale.truncate(), &walls);
+    ghost_transform.translation = ball.position;
+    ghost.velocity = ball.velocity;
+
+    if let Ok(hole_transform) = hole_query.get_single() {
+        if ball_in_hole(ball.position, hole_transform.translation) {
+            ghost.velocity = Vec2::ZERO;
+            ghost.sunk = true;
+        }
+    }
+}
+
+pub fn update_ghost_looks(
+    settings: Res<Settings>,
+    mut ghost_query: Query<(&Ghost, &mut Visibility, &Handle<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (ghost, mut visibility, material) in ghost_query.iter_mut() {
+        *visibility = if settings.show_ghost && !ghost.sunk {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if settings.is_changed() {
+            if let Some(material) = materials.get_mut(material) {
+                material.color = ghost_color(&settings);
+            }
+        }
+    }
+}
+
+pub fn despawn_ghost(mut commands: Commands, ghost_query: Query<Entity, With<Ghost>>) {
+    for entity in ghost_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
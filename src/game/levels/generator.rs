@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+use crate::game::ai::solver;
+use crate::game::ai::SplitMix;
+use crate::game::levels::layout::{BoxLayout, LevelLayout};
+
+// Inside measures of a generated hole, the largest one still fits the screen
+const MIN_SIZE: Vec2 = Vec2::new(800.0, 400.0);
+const MAX_SIZE: Vec2 = Vec2::new(1600.0, 900.0);
+const WALL_THICKNESS: f32 = 30.0;
+// How far in from the ends of the hole the tee and the cup sit
+const END_MARGIN: f32 = 80.0;
+// Kept free around the tee, the cup and between obstacles, wide enough for the ball
+// to get through, so no obstacle can close off part of the hole
+const CLEARANCE: f32 = 50.0;
+const MAX_WALLS: usize = 3;
+const MAX_PONDS: usize = 2;
+const MIN_POND: f32 = 60.0;
+const MAX_POND: f32 = 180.0;
+// Obstacles are placed at random, one that doesn't fit is left out
+const PLACEMENT_TRIES: usize = 8;
+// Random holes tried before settling for one without obstacles
+const MAX_ATTEMPTS: usize = 8;
+
+#[derive(Clone)]
+pub struct GeneratedHole {
+    pub layout: LevelLayout,
+    pub par: usize,
+}
+
+// Seed of hole `level` of an endless course
+pub fn hole_seed(seed: u64, level: i32) -> u64 {
+    SplitMix(seed.wrapping_add(level as u64)).next_u64()
+}
+
+// Random holes until one the solver can finish. Every attempt draws from the same
+// generator, so a seed always ends up with the same hole.
+pub fn generate(seed: u64) -> GeneratedHole {
+    let mut rng = SplitMix(seed);
+    for _ in 0..MAX_ATTEMPTS {
+        let layout = random_layout(&mut rng, true);
+        if let Some(par) = solver::solve(&layout).par() {
+            return GeneratedHole { layout, par };
+        }
+    }
+    // Nothing in the way, any putt long enough gets there
+    let layout = random_layout(&mut rng, false);
+    match solver::solve(&layout).par() {
+        Some(par) => GeneratedHole { layout, par },
+        None => fallback_hole(),
+    }
+}
+
+// The smallest hole with the cup straight across from the tee, in one putt and so a
+// par of two, as the tests check. Only for when no random hole works out, so every
+// seed has a par.
+pub fn fallback_hole() -> GeneratedHole {
+    let inner = MIN_SIZE / 2.0 - Vec2::splat(END_MARGIN);
+    GeneratedHole {
+        layout: LevelLayout {
+            ball: Vec2::new(-inner.x, 0.0),
+            hole: Vec2::new(inner.x, 0.0),
+            walls: boundary(MIN_SIZE),
+            water: Vec::new(),
+        },
+        par: 2,
+    }
+}
+
+fn random_layout(rng: &mut SplitMix, obstacles: bool) -> LevelLayout {
+    let size = Vec2::new(
+        rng.range(MIN_SIZE.x, MAX_SIZE.x),
+        rng.range(MIN_SIZE.y, MAX_SIZE.y),
+    )
+    .round();
+    let half = size / 2.0;
+    let inner = half - Vec2::splat(END_MARGIN);
+
+    // Tee at one end and the cup in the far half, either way round
+    let side = if rng.below(2) == 0 { 1.0 } else { -1.0 };
+    let ball = Vec2::new(-side * inner.x, rng.range(-inner.y, inner.y)).round();
+    let hole = Vec2::new(
+        side * rng.range(inner.x / 2.0, inner.x),
+        rng.range(-inner.y, inner.y),
+    )
+    .round();
+
+    let mut layout = LevelLayout {
+        ball,
+        hole,
+        walls: boundary(size),
+        water: Vec::new(),
+    };
+    if !obstacles {
+        return layout;
+    }
+    let boundary_walls = layout.walls.len();
+    for _ in 0..=rng.below(MAX_WALLS) {
+        let wall = (0..PLACEMENT_TRIES)
+            .map(|_| inner_wall(rng, size))
+            .find(|wall| fits(&layout, wall, &layout.walls[boundary_walls..]));
+        layout.walls.extend(wall);
+    }
+    for _ in 0..rng.below(MAX_PONDS + 1) {
+        let pond = (0..PLACEMENT_TRIES)
+            .map(|_| pond(rng, inner))
+            .find(|pond| fits(&layout, pond, &layout.walls));
+        layout.water.extend(pond);
+    }
+    layout
+}
+
+// Outer walls around a hole of `size`, centered on the origin
+fn boundary(size: Vec2) -> Vec<BoxLayout> {
+    let half = size / 2.0;
+    let width = size.x + WALL_THICKNESS;
+    let height = size.y + WALL_THICKNESS;
+    vec![
+        BoxLayout {
+            center: Vec2::new(-half.x, 0.0),
+            size: Vec2::new(WALL_THICKNESS, height),
+        },
+        BoxLayout {
+            center: Vec2::new(half.x, 0.0),
+            size: Vec2::new(WALL_THICKNESS, height),
+        },
+        BoxLayout {
+            center: Vec2::new(0.0, -half.y),
+            size: Vec2::new(width, WALL_THICKNESS),
+        },
+        BoxLayout {
+            center: Vec2::new(0.0, half.y),
+            size: Vec2::new(width, WALL_THICKNESS),
+        },
+    ]
+}
+
+// A wall sticking out of one of the sides, like the middle wall of the first level
+fn inner_wall(rng: &mut SplitMix, size: Vec2) -> BoxLayout {
+    let half = size / 2.0;
+    let from = if rng.below(2) == 0 { 1.0 } else { -1.0 };
+    if rng.below(2) == 0 {
+        let length = size.y * rng.range(0.35, 0.65);
+        BoxLayout {
+            center: Vec2::new(
+                rng.range(-half.x * 0.6, half.x * 0.6),
+                from * (half.y - length / 2.0),
+            )
+            .round(),
+            size: Vec2::new(WALL_THICKNESS, length).round(),
+        }
+    } else {
+        let length = size.x * rng.range(0.3, 0.6);
+        BoxLayout {
+            center: Vec2::new(
+                from * (half.x - length / 2.0),
+                rng.range(-half.y * 0.5, half.y * 0.5),
+            )
+            .round(),
+            size: Vec2::new(length, WALL_THICKNESS).round(),
+        }
+    }
+}
+
+fn pond(rng: &mut SplitMix, inner: Vec2) -> BoxLayout {
+    BoxLayout {
+        center: Vec2::new(rng.range(-inner.x, inner.x), rng.range(-inner.y, inner.y)).round(),
+        size: Vec2::new(rng.range(MIN_POND, MAX_POND), rng.range(MIN_POND, MAX_POND)).round(),
+    }
+}
+
+// Clear of the tee, the cup, every pond and the given walls
+fn fits(layout: &LevelLayout, obstacle: &BoxLayout, walls: &[BoxLayout]) -> bool {
+    let area = Rect::from_center_size(obstacle.center, obstacle.size + 2.0 * CLEARANCE);
+    let clear_of = |other: &BoxLayout| {
+        area.intersect(Rect::from_center_size(other.center, other.size))
+            .is_empty()
+    };
+    !area.contains(layout.ball)
+        && !area.contains(layout.hole)
+        && walls.iter().all(clear_of)
+        && layout.water.iter().all(clear_of)
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::components::{Ball, Collider, GolfHole, Water};
+use crate::game::levels::{BallBundle, GolfHoleBundle};
+use crate::game::physics::{self, Wall};
+use crate::game::players::Player;
+
+// Where the built-in levels put the ball
+const BALL_Z: f32 = 2.0;
+// Over the walls, under the ghost and the balls
+const WATER_Z: f32 = 0.5;
+
+// A hole as plain data, how level files describe one:
+//
+//     (
+//         ball: (-370.0, -100.0),
+//         hole: (350.0, 100.0),
+//         walls: [
+//             (center: (-400.0, 0.0), size: (30.0, 330.0)),
+//         ],
+//         water: [
+//             (center: (0.0, -50.0), size: (120.0, 80.0)),
+//         ],
+//     )
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LevelLayout {
+    pub ball: Vec2,
+    pub hole: Vec2,
+    pub walls: Vec<BoxLayout>,
+    // Most holes have no ponds
+    #[serde(default)]
+    pub water: Vec<BoxLayout>,
+}
+
+// Center and full size, like the wall and pond sprites
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoxLayout {
+    pub center: Vec2,
+    pub size: Vec2,
+}
+
+impl LevelLayout {
+    pub fn from_ron(text: &str) -> Result<LevelLayout, ron::error::SpannedError> {
+        ron::from_str(text)
+    }
+
+    // Walls the way `step_ball` sorts them
+    pub fn walls(&self) -> Vec<Wall> {
+        physics::sorted_walls(self.walls.iter().map(|wall| Wall {
+            center: wall.center.extend(0.0),
+            size: wall.size,
+        }))
+    }
+
+    pub fn ponds(&self) -> Vec<Rect> {
+        self.water
+            .iter()
+            .map(|pond| Rect::from_center_size(pond.center, pond.size))
+            .collect()
+    }
+
+    // Smallest rectangle holding every wall, like `LevelBounds`
+    pub fn bounds(&self) -> Rect {
+        self.walls
+            .iter()
+            .map(|wall| Rect::from_center_size(wall.center, wall.size))
+            .reduce(|bounds, wall| bounds.union(wall))
+            .unwrap_or_default()
+    }
+
+    // The hole loaded in `world`, with the first player's ball as the tee
+    pub fn from_world(world: &mut World) -> Option<LevelLayout> {
+        let ball = world
+            .query_filtered::<(&Transform, Option<&Player>), With<Ball>>()
+            .iter(world)
+            .min_by_key(|(_, player)| player.copied().unwrap_or_default().0)?
+            .0
+            .translation
+            .truncate();
+        let hole = world
+            .query_filtered::<&Transform, With<GolfHole>>()
+            .get_single(world)
+            .ok()?
+            .translation
+            .truncate();
+        let walls = world
+            .query_filtered::<&Transform, With<Collider>>()
+            .iter(world)
+            .map(|transform| BoxLayout {
+                center: transform.translation.truncate(),
+                size: transform.scale.truncate(),
+            })
+            .collect();
+        let water = world
+            .query_filtered::<&Transform, With<Water>>()
+            .iter(world)
+            .map(|transform| BoxLayout {
+                center: transform.translation.truncate(),
+                size: transform.scale.truncate(),
+            })
+            .collect();
+        Some(LevelLayout {
+            ball,
+            hole,
+            walls,
+            water,
+        })
+    }
+
+    // Spawns the hole like the built-in levels do
+    pub fn spawn(&self, commands: &mut Commands) {
+        commands.spawn(GolfHoleBundle::new(self.hole.extend(0.0)));
+        commands.spawn(BallBundle::new(self.ball.extend(BALL_Z), Vec2::ZERO));
+        for wall in &self.walls {
+            commands.spawn((box_sprite(wall, 0.0), Collider));
+        }
+        for pond in &self.water {
+            commands.spawn((box_sprite(pond, WATER_Z), Water));
+        }
+    }
+}
+
+// Painted by `apply_palette` once spawned
+fn box_sprite(area: &BoxLayout, z: f32) -> SpriteBundle {
+    SpriteBundle {
+        transform: Transform {
+            translation: area.center.extend(z),
+            scale: area.size.extend(1.0),
+            ..default()
+        },
+        ..default()
+    }
+}
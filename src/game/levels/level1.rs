@@ -1,18 +1,9 @@
 
-use bevy::{
-    prelude::*,
-    sprite::MaterialMesh2dBundle,
-};
+use bevy::prelude::*;
 
 use crate::game::GameState;
-use crate::game::GolfHole;
-use crate::game::Ball;
-use crate::game::Velocity;
+use crate::game::levels::{BallBundle, GolfHoleBundle};
 use crate::game::Collider;
-use crate::GOLF_HOLE_COLOR;
-use crate::GOLF_HOLE_SIZE;
-use crate::BALL_COLOR;
-use crate::BALL_SIZE;
 
 const GOLF_HOLE_STARTING_POSITION: Vec3 = Vec3::new(350.0, 100.0, 0.0);
 const BALL_SPEED: f32 = 500.0;
@@ -67,32 +58,15 @@ impl WallBundle {
 
 pub fn load_level_1(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
     mut app_state_next_state: ResMut<NextState<GameState>>,
 ) {
     // Golf Hole
-    commands.spawn((
-        MaterialMesh2dBundle {
-            mesh: meshes.add(shape::Circle::default().into()).into(),
-            material: materials.add(ColorMaterial::from(GOLF_HOLE_COLOR)),
-            transform: Transform::from_translation(GOLF_HOLE_STARTING_POSITION)
-                .with_scale(GOLF_HOLE_SIZE),
-            ..default()
-        },
-        GolfHole,
-    ));
+    commands.spawn(GolfHoleBundle::new(GOLF_HOLE_STARTING_POSITION));
 
     // Ball
-    commands.spawn((
-        MaterialMesh2dBundle {
-            mesh: meshes.add(shape::Circle::default().into()).into(),
-            material: materials.add(ColorMaterial::from(BALL_COLOR)),
-            transform: Transform::from_translation(BALL_STARTING_POSITION).with_scale(BALL_SIZE),
-            ..default()
-        },
-        Ball,
-        Velocity(INITIAL_BALL_DIRECTION.normalize() * BALL_SPEED),
+    commands.spawn(BallBundle::new(
+        BALL_STARTING_POSITION,
+        INITIAL_BALL_DIRECTION.normalize() * BALL_SPEED,
     ));
 
     let arena_height = TOP_WALL - BOTTOM_WALL;
@@ -105,6 +79,5 @@ pub fn load_level_1(
     commands.spawn(WallBundle::new(Vec2::new(0., BOTTOM_WALL), Vec2::new(arena_width + WALL_THICKNESS, WALL_THICKNESS)));
     commands.spawn(WallBundle::new(Vec2::new(0., TOP_WALL), Vec2::new(arena_width + WALL_THICKNESS, WALL_THICKNESS)));
 
-    app_state_next_state.set(GameState::DeadBall);
-    println!("Entered AppState::DeadBall");
+    app_state_next_state.set(GameState::Flyover);
 }
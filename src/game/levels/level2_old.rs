@@ -111,5 +111,4 @@ pub fn load_level_2(
     commands.spawn(WallBundle::new(Vec2::new(-150., TOP_BOTTOM_WALL), Vec2::new(arena_width + WALL_THICKNESS - 300., WALL_THICKNESS)));
 
     app_state_next_state.set(AppState::DeadBall);
-    println!("Entered AppState::DeadBall");
 }
@@ -1,18 +1,9 @@
 
-use bevy::{
-    prelude::*,
-    sprite::MaterialMesh2dBundle,
-};
+use bevy::prelude::*;
 
 use crate::game::GameState;
-use crate::game::GolfHole;
-use crate::game::Ball;
-use crate::game::Velocity;
+use crate::game::levels::{BallBundle, GolfHoleBundle};
 use crate::game::Collider;
-use crate::GOLF_HOLE_COLOR;
-use crate::GOLF_HOLE_SIZE;
-use crate::BALL_COLOR;
-use crate::BALL_SIZE;
 
 const GOLF_HOLE_STARTING_POSITION: Vec3 = Vec3::new(0.0, 0.0, 0.0);
 const BALL_SPEED: f32 = 500.0;
@@ -20,7 +11,6 @@ const BALL_STARTING_POSITION: Vec3 = Vec3::new(0.0, -250.0, 2.0);
 const INITIAL_BALL_DIRECTION: Vec2 = Vec2::new(0.5, -0.5);
 const WALL_THICKNESS: f32 = 30.0;
 const WALL_THICKNESS_H: f32 = 15.0;
-const WALL_THICKNESS_D: f32 = 60.0;
 const WALL_COLOR: Color = Color::rgb(0.8, 0.8, 0.8);
 
 // This bundle is a collection of the components that define a "wall" in our game
@@ -62,40 +52,23 @@ impl WallBundle {
 
 pub fn load_level_3(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
     mut app_state_next_state: ResMut<NextState<GameState>>,
 ) {
     // Golf Hole
-    commands.spawn((
-        MaterialMesh2dBundle {
-            mesh: meshes.add(shape::Circle::default().into()).into(),
-            material: materials.add(ColorMaterial::from(GOLF_HOLE_COLOR)),
-            transform: Transform::from_translation(GOLF_HOLE_STARTING_POSITION)
-                .with_scale(GOLF_HOLE_SIZE),
-            ..default()
-        },
-        GolfHole,
-    ));
+    commands.spawn(GolfHoleBundle::new(GOLF_HOLE_STARTING_POSITION));
 
     // Ball
 
-    commands.spawn((
-        MaterialMesh2dBundle {
-            mesh: meshes.add(shape::Circle::default().into()).into(),
-            material: materials.add(ColorMaterial::from(BALL_COLOR)),
-            transform: Transform::from_translation(BALL_STARTING_POSITION).with_scale(BALL_SIZE),
-            ..default()
-        },
-        Ball,
-        Velocity(INITIAL_BALL_DIRECTION.normalize() * BALL_SPEED),
+    commands.spawn(BallBundle::new(
+        BALL_STARTING_POSITION,
+        INITIAL_BALL_DIRECTION.normalize() * BALL_SPEED,
     ));
 
     //let arena_height = TOP_WALL_Y - BOTTOM_WALL_Y;
     //let arena_width = RIGHT_WALL_X - LEFT_WALL_X;
     //let mut walls: Vec<[[f32; 2]; 2]> 
 
-    let mut walls = vec![[[0.,-300.],[900.,WALL_THICKNESS]],[[0.,300.],[900.,WALL_THICKNESS]],
+    let walls = vec![[[0.,-300.],[900.,WALL_THICKNESS]],[[0.,300.],[900.,WALL_THICKNESS]],
                                              [[0.,-200.],[700.,WALL_THICKNESS]],[[0.,200.],[700.,WALL_THICKNESS]],
                                              [[0.,-100.],[500.,WALL_THICKNESS]],[[0.,100.],[500.,WALL_THICKNESS]],
                                              [[0.,-35.],[300.,WALL_THICKNESS_H]],[[0.,35.],[300.,WALL_THICKNESS_H]],
@@ -110,6 +83,5 @@ pub fn load_level_3(
     }
 
 
-    app_state_next_state.set(GameState::DeadBall);
-    println!("Entered AppState::DeadBall");
+    app_state_next_state.set(GameState::Flyover);
 }
@@ -0,0 +1,94 @@
+
+use bevy::prelude::*;
+
+use crate::game::GameState;
+use crate::game::levels::{BallBundle, GolfHoleBundle};
+use crate::game::Collider;
+
+const GOLF_HOLE_STARTING_POSITION: Vec3 = Vec3::new(1350.0, -700.0, 0.0);
+const BALL_SPEED: f32 = 500.0;
+const BALL_STARTING_POSITION: Vec3 = Vec3::new(-1550.0, -750.0, 2.0);
+const INITIAL_BALL_DIRECTION: Vec2 = Vec2::new(0.5, -0.5);
+const WALL_THICKNESS: f32 = 30.0;
+const WALL_COLOR: Color = Color::rgb(0.8, 0.8, 0.8);
+
+// This bundle is a collection of the components that define a "wall" in our game
+#[derive(Bundle)]
+struct WallBundle {
+    // You can nest bundles inside of other bundles like this
+    // Allowing you to compose their functionality
+    sprite_bundle: SpriteBundle,
+    collider: Collider,
+}
+
+
+impl WallBundle {
+    // This "builder method" allows us to reuse logic across our wall entities,
+    // making our code easier to read and less prone to bugs when we change the logic
+    fn new(translation: Vec2, scale: Vec2) -> WallBundle {
+        WallBundle {
+            sprite_bundle: SpriteBundle {
+                transform: Transform {
+                    // We need to convert our Vec2 into a Vec3, by giving it a z-coordinate
+                    // This is used to determine the order of our sprites
+                    translation: translation.extend(0.0),
+                    // The z-scale of 2D objects must always be 1.0,
+                    // or their ordering will be affected in surprising ways.
+                    // See https://github.com/bevyengine/bevy/issues/4149
+                    scale: scale.extend(1.0),
+                    ..default()
+                },
+                sprite: Sprite {
+                    color: WALL_COLOR,
+                    ..default()
+                },
+                ..default()
+            },
+            collider: Collider,
+        }
+    }
+}
+
+// Too big for one screen, the camera has to follow the ball around it
+const LEFT_WALL: f32 = -1800.;
+const RIGHT_WALL: f32 = 1800.;
+const BOTTOM_WALL: f32 = -1000.;
+const TOP_WALL: f32 = 1000.;
+
+pub fn load_level_4(
+    mut commands: Commands,
+    mut app_state_next_state: ResMut<NextState<GameState>>,
+) {
+    // Golf Hole
+    commands.spawn(GolfHoleBundle::new(GOLF_HOLE_STARTING_POSITION));
+
+    // Ball
+    commands.spawn(BallBundle::new(
+        BALL_STARTING_POSITION,
+        INITIAL_BALL_DIRECTION.normalize() * BALL_SPEED,
+    ));
+
+    let arena_height = TOP_WALL - BOTTOM_WALL;
+    let arena_width = RIGHT_WALL - LEFT_WALL;
+
+    // Outer walls, then three staggered walls that make the ball zigzag across the course
+    let walls = vec![[[LEFT_WALL, 0.],[WALL_THICKNESS, arena_height + WALL_THICKNESS]],
+                     [[RIGHT_WALL, 0.],[WALL_THICKNESS, arena_height + WALL_THICKNESS]],
+                     [[0., BOTTOM_WALL],[arena_width + WALL_THICKNESS, WALL_THICKNESS]],
+                     [[0., TOP_WALL],[arena_width + WALL_THICKNESS, WALL_THICKNESS]],
+                     [[-900., -300.],[WALL_THICKNESS, 1400.]],
+                     [[0., 300.],[WALL_THICKNESS, 1400.]],
+                     [[900., -300.],[WALL_THICKNESS, 1400.]],
+                     [[-450., 500.],[200., WALL_THICKNESS]],
+                     [[450., -500.],[200., WALL_THICKNESS]],
+                     [[1350., -300.],[300., WALL_THICKNESS]]];
+
+    // Walls
+    for wall in walls {
+        let wall_pos = wall[0];
+        let wall_size = wall[1];
+        commands.spawn(WallBundle::new(Vec2::new(wall_pos[0], wall_pos[1]), Vec2::new(wall_size[0], wall_size[1])));
+    }
+
+    app_state_next_state.set(GameState::Flyover);
+}
@@ -1,4 +1,7 @@
-use bevy::{prelude::*, transform::commands};
+use bevy::prelude::*;
+use bevy::tasks::{block_on, AsyncComputeTaskPool, Task};
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
 
 mod level1;
 use level1::*;
@@ -6,12 +9,142 @@ mod level2;
 use level2::*;
 mod level3;
 use level3::*;
+mod level4;
+use level4::*;
 
-use crate::GameState;
+pub mod layout;
+
+pub mod generator;
+
+use generator::GeneratedHole;
+
+use crate::game::components::{Ball, Collider, GolfHole, Velocity};
+use crate::{AppState, GameState};
+use crate::{BALL_SIZE, GOLF_HOLE_SIZE};
 
 #[derive(Resource, PartialEq)]
 pub struct Level(pub i32);
 
+// Which holes a round goes through
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Course {
+    // The built-in holes, up to `LAST_LEVEL`
+    #[default]
+    Classic,
+    // A generated hole after another, the same seed always builds the same ones
+    Endless { seed: u64 },
+}
+
+impl Course {
+    // Seeded from how long the game has been open, so every run is a new one
+    pub fn new_endless(time: &Time<Real>) -> Course {
+        Course::Endless {
+            seed: time.elapsed().as_nanos() as u64,
+        }
+    }
+}
+
+pub fn classic_course(course: Res<Course>) -> bool {
+    *course == Course::Classic
+}
+
+pub fn endless_course(course: Res<Course>) -> bool {
+    !classic_course(course)
+}
+
+// The last level, finishing it ends the round
+pub const LAST_LEVEL: i32 = 4;
+
+// Smallest rectangle holding every wall of the loaded hole, in world units
+#[derive(Resource, Default)]
+pub struct LevelBounds(pub Rect);
+
+// Par of the generated hole being played, the built-in holes have none
+#[derive(Resource, Default)]
+pub struct HolePar(pub Option<usize>);
+
+// Holes of the endless course by seed and level, kept so restarting a hole or jumping
+// around its replay doesn't build it again
+#[derive(Resource, Default)]
+pub struct GeneratedHoles {
+    holes: HashMap<(u64, i32), GeneratedHole>,
+    // On the `AsyncComputeTaskPool`, solving a hole takes far longer than a frame
+    building: HashMap<(u64, i32), Task<GeneratedHole>>,
+}
+
+impl GeneratedHoles {
+    pub fn get(&self, seed: u64, level: i32) -> Option<&GeneratedHole> {
+        self.holes.get(&(seed, level))
+    }
+
+    // Starts building the hole unless it's built or on its way. Holes of any other
+    // seed are dropped, a new course never comes back to them.
+    pub fn build(&mut self, seed: u64, level: i32) {
+        self.holes.retain(|(hole_seed, _), _| *hole_seed == seed);
+        self.building.retain(|(hole_seed, _), _| *hole_seed == seed);
+        let key = (seed, level);
+        if self.holes.contains_key(&key) || self.building.contains_key(&key) {
+            return;
+        }
+        let task = AsyncComputeTaskPool::get()
+            .spawn(async move { generator::generate(generator::hole_seed(seed, level)) });
+        self.building.insert(key, task);
+    }
+
+    // Keeps the holes that are done building
+    pub fn collect_built(&mut self) {
+        let built: Vec<(u64, i32)> = self
+            .building
+            .iter()
+            .filter(|(_, task)| task.is_finished())
+            .map(|(key, _)| *key)
+            .collect();
+        for key in built {
+            if let Some(task) = self.building.remove(&key) {
+                self.holes.insert(key, block_on(task));
+            }
+        }
+    }
+}
+
+// Levels only spawn what the simulation needs, `add_ball_and_hole_meshes` gives the
+// ball and the hole their looks when there is something to draw them with
+#[derive(Bundle)]
+pub struct BallBundle {
+    spatial_bundle: SpatialBundle,
+    ball: Ball,
+    velocity: Velocity,
+}
+
+impl BallBundle {
+    pub fn new(translation: Vec3, velocity: Vec2) -> BallBundle {
+        BallBundle {
+            spatial_bundle: SpatialBundle::from_transform(
+                Transform::from_translation(translation).with_scale(BALL_SIZE),
+            ),
+            ball: Ball,
+            velocity: Velocity(velocity),
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct GolfHoleBundle {
+    spatial_bundle: SpatialBundle,
+    golf_hole: GolfHole,
+}
+
+impl GolfHoleBundle {
+    pub fn new(translation: Vec3) -> GolfHoleBundle {
+        GolfHoleBundle {
+            spatial_bundle: SpatialBundle::from_transform(
+                Transform::from_translation(translation).with_scale(GOLF_HOLE_SIZE),
+            ),
+            golf_hole: GolfHole,
+        }
+    }
+}
+
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub struct LevelsPlugins;
 
@@ -20,6 +153,10 @@ const BACKGROUND_COLOR: Color = Color::rgb(0.0, 0.533333, 0.329412);
 impl Plugin for LevelsPlugins {
     fn build(&self, app: &mut App) {
         app.insert_resource(Level(1))
+            .init_resource::<Course>()
+            .init_resource::<HolePar>()
+            .init_resource::<GeneratedHoles>()
+            .init_resource::<LevelBounds>()
             .insert_resource(ClearColor(BACKGROUND_COLOR))
             // OnEnter Systems
             .add_systems(
@@ -27,9 +164,75 @@ impl Plugin for LevelsPlugins {
                 (
                     load_level_1.run_if(resource_equals(Level(1))),
                     load_level_2.run_if(resource_equals(Level(2))),
-                    load_level_3.run_if(resource_equals(Level(3)))
-                ),
-            );
+                    load_level_3.run_if(resource_equals(Level(3))),
+                    load_level_4.run_if(resource_equals(Level(4))),
+                )
+                    .run_if(classic_course),
+            )
+            .add_systems(
+                OnEnter(GameState::LoadingMap),
+                build_generated_level.run_if(endless_course),
+            )
+            .add_systems(
+                Update,
+                load_generated_level
+                    .run_if(in_state(GameState::LoadingMap))
+                    .run_if(endless_course),
+            )
+            .add_systems(OnEnter(AppState::Game), clear_hole_par)
+            .add_systems(OnEnter(GameState::Flyover), compute_level_bounds);
     }
 }
 
+
+pub fn compute_level_bounds(
+    collider_query: Query<&Transform, With<Collider>>,
+    mut level_bounds: ResMut<LevelBounds>,
+) {
+    level_bounds.0 = collider_query
+        .iter()
+        .map(|transform| {
+            Rect::from_center_size(transform.translation.truncate(), transform.scale.truncate())
+        })
+        .reduce(|bounds, wall| bounds.union(wall))
+        .unwrap_or_default();
+}
+
+pub fn clear_hole_par(mut hole_par: ResMut<HolePar>) {
+    hole_par.0 = None;
+}
+
+// The hole of this level on an endless course, built from the seed and the level so
+// a replay builds the very same one
+pub fn build_generated_level(
+    course: Res<Course>,
+    level: Res<Level>,
+    mut generated_holes: ResMut<GeneratedHoles>,
+) {
+    if let Course::Endless { seed } = *course {
+        generated_holes.build(seed, level.0);
+    }
+}
+
+// Waits in `LoadingMap` until the hole is built, then starts on the next one while
+// this one is played
+pub fn load_generated_level(
+    mut commands: Commands,
+    course: Res<Course>,
+    level: Res<Level>,
+    mut generated_holes: ResMut<GeneratedHoles>,
+    mut hole_par: ResMut<HolePar>,
+    mut app_state_next_state: ResMut<NextState<GameState>>,
+) {
+    let Course::Endless { seed } = *course else {
+        return;
+    };
+    generated_holes.collect_built();
+    let Some(hole) = generated_holes.get(seed, level.0) else {
+        return;
+    };
+    hole_par.0 = Some(hole.par);
+    hole.layout.spawn(&mut commands);
+    app_state_next_state.set(GameState::Flyover);
+    generated_holes.build(seed, level.0 + 1);
+}
@@ -1,9 +1,4 @@
-use bevy::{
-    prelude::*,
-    sprite::collide_aabb::{collide, Collision},
-    sprite::MaterialMesh2dBundle,
-    transform::commands,
-};
+use bevy::prelude::*;
 
 pub mod systems;
 use systems::*;
@@ -11,52 +6,115 @@ use systems::*;
 pub mod components;
 use components::*;
 
+pub mod physics;
+
 pub mod swings_count;
 use swings_count::*;
 
 pub mod levels;
 use levels::*;
 
-use crate::AppState;
-use crate::GameOver;
+pub mod retry;
+use retry::*;
+
+pub mod aiming;
+use aiming::*;
+
+pub mod pause;
+
+pub mod replay;
+
+pub mod ghost;
+use ghost::{GhostPlugin, PersonalBestPlugin};
+
+pub mod players;
+use players::PlayersPlugin;
+
+pub mod ai;
+use ai::AiPlugin;
+use replay::viewer::ReplayViewerPlugin;
+use replay::ReplayPlugin;
+pub use pause::PauseState;
+use pause::PausePlugin;
+
+use crate::{course_shown, AppState, GameOver};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
 pub enum GameState {
     #[default]
     OutOfGame,
     LoadingMap,
+    // Camera shows the new hole before the first swing
+    Flyover,
     DeadBall,
     BallMoving,
     UnloadingMap,
+    RestartingHole,
 }
 
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub struct GamePlugin;
 
-const BACKGROUND_COLOR: Color = Color::rgb(0.0, 0.533333, 0.329412);
-
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(ClearColor(Color::rgb_u8(43, 44, 47)))
-            .add_event::<CollisionEvent>()
-            .add_event::<GameOver>()
+            .add_plugins(SimulationPlugin)
             .add_plugins(SwingsPlugins)
-            .add_state::<GameState>()
-            .add_plugins(LevelsPlugins)
+            .add_plugins(RetryPlugin)
+            .add_plugins(AimingPlugin)
+            .add_plugins(PausePlugin)
+            .add_plugins(ReplayViewerPlugin)
+            .add_plugins(GhostPlugin)
             .add_systems(OnEnter(AppState::Game), enter_game_state)
-            .add_systems(OnEnter(GameState::DeadBall), spawn_velocity_vector)
+            .add_systems(
+                PostUpdate,
+                (add_ball_and_hole_meshes, apply_palette)
+                    .chain()
+                    .run_if(course_shown),
+            )
+            .add_systems(
+                OnEnter(GameState::DeadBall),
+                spawn_velocity_vector.run_if(in_state(AppState::Game)),
+            )
             .add_systems(
                 FixedUpdate,
-                (set_ball_velocity, update_scoreboard).chain()
-                    .run_if(in_state(GameState::DeadBall))
-                    .chain(),
+                update_scoreboard.run_if(in_state(GameState::DeadBall)),
+            )
+            .add_systems(OnExit(GameState::DeadBall), unspawn_velocity_vector);
+    }
+}
+
+// Everything that decides how a round plays out, with nothing to draw and no input
+// to read, so it also runs under `MinimalPlugins`. Shots come in as `Swing` events.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<AppState>()
+            .add_state::<GameState>()
+            .insert_resource(Time::<Fixed>::from_hz(physics::TICKS_PER_SECOND))
+            .add_event::<CollisionEvent>()
+            .add_event::<GameOver>()
+            .add_event::<Swing>()
+            .add_plugins(LevelsPlugins)
+            .add_plugins(ReplayPlugin)
+            .add_plugins(PersonalBestPlugin)
+            .add_plugins(PlayersPlugin)
+            .add_plugins(AiPlugin)
+            .init_resource::<LastSwing>()
+            .add_systems(OnEnter(AppState::Game), (insert_score, start_round))
+            .add_systems(OnExit(AppState::Game), remove_score)
+            .add_systems(OnEnter(GameState::LoadingMap), clear_last_swing)
+            .add_systems(Update, swing_ball.run_if(in_state(GameState::DeadBall)))
+            .add_systems(
+                OnEnter(GameState::RestartingHole),
+                (unload_map, reset_hole_score, reload_map).chain(),
             )
-            .add_systems(OnExit(GameState::DeadBall), unspawn_velocity_vector)
             .add_systems(
                 FixedUpdate,
                 (
-                    apply_velocity,
-                    uptade_ball_velocity,
+                    step_ball,
+                    check_ball_in_water,
                     check_ball_inside_hole,
                     // play_collision_sound,
                 )
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState};
+use crate::settings::{AimingMode, Settings};
+use crate::ui::settings_menu::SettingsMenuState;
+use crate::AppState;
+
+// Pausing is tracked apart from `GameState` so the ball keeps its state underneath
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<PauseState>()
+            .add_systems(
+                Update,
+                toggle_pause
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SettingsMenuState::Closed)),
+            )
+            .add_systems(OnEnter(PauseState::Paused), pause_time)
+            .add_systems(OnExit(PauseState::Paused), resume_time)
+            .add_systems(OnExit(AppState::Game), reset_pause);
+    }
+}
+
+pub fn toggle_pause(
+    action_state: Res<ActionState>,
+    pause_state: Res<State<PauseState>>,
+    mut pause_state_next_state: ResMut<NextState<PauseState>>,
+) {
+    if !action_state.just_pressed(Action::Pause) {
+        return;
+    }
+    match pause_state.get() {
+        PauseState::Running => {
+            pause_state_next_state.set(PauseState::Paused);
+        }
+        PauseState::Paused => {
+            pause_state_next_state.set(PauseState::Running);
+        }
+    }
+}
+
+// Stopping virtual time also stops the fixed timestep, so the ball freezes mid roll
+pub fn pause_time(mut time: ResMut<Time<Virtual>>, mut window_query: Query<&mut Window>) {
+    time.pause();
+    window_query.single_mut().cursor.visible = true;
+}
+
+pub fn resume_time(
+    mut time: ResMut<Time<Virtual>>,
+    mut window_query: Query<&mut Window>,
+    app_state: Res<State<AppState>>,
+    settings: Res<Settings>,
+) {
+    time.unpause();
+    if app_state.get() == &AppState::Game {
+        window_query.single_mut().cursor.visible = settings.aiming_mode == AimingMode::Slingshot;
+    }
+}
+
+pub fn reset_pause(mut pause_state_next_state: ResMut<NextState<PauseState>>) {
+    pause_state_next_state.set(PauseState::Running);
+}
@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::{collide, Collision};
+
+// Pure ball physics, shared by the fixed timestep systems and the trajectory preview
+// so the preview can never drift away from what the ball actually does.
+// Everything advances by the same fixed `DT`, never by the frame time, so a shot
+// from the same state always ends bit-identical.
+
+// Physics ticks per second, `SimulationPlugin` sets `Time<Fixed>` to this
+pub const TICKS_PER_SECOND: f64 = 64.0;
+// Length of one tick, exact in binary so it adds no rounding of its own
+pub const DT: f32 = 1.0 / 64.0;
+
+// Speed lost per second, split between the axes
+const FRICTION: f32 = 176.0;
+// Below this speed on both axes the ball is considered stopped
+const STOP_SPEED: f32 = 5.0;
+
+// Axis aligned box, center and full size, like the wall sprites
+#[derive(Clone, Copy, Debug)]
+pub struct Wall {
+    pub center: Vec3,
+    pub size: Vec2,
+}
+
+// Ball position and velocity, all the state a tick reads and writes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BallState {
+    pub position: Vec3,
+    pub velocity: Vec2,
+}
+
+// What happened during a tick
+#[derive(Clone, Debug, Default)]
+pub struct StepOutcome {
+    // Every side touched, also by a ball resting against or sliding along it
+    pub normals: Vec<Vec2>,
+    // Sides the ball actually bounced off, its velocity flipped against them
+    pub bounces: usize,
+    pub stopped: bool,
+}
+
+// Walls sorted by position, so the bounce order never depends on entity order
+pub fn sorted_walls(walls: impl IntoIterator<Item = Wall>) -> Vec<Wall> {
+    let mut walls: Vec<Wall> = walls.into_iter().collect();
+    walls.sort_by(|a, b| {
+        a.center
+            .x
+            .total_cmp(&b.center.x)
+            .then(a.center.y.total_cmp(&b.center.y))
+            .then(a.size.x.total_cmp(&b.size.x))
+            .then(a.size.y.total_cmp(&b.size.y))
+    });
+    walls
+}
+
+// One physics tick: move, bounce, then either stop or lose speed to friction.
+// A stopped ball is left at rest, so ticks that run before the state changes to
+// `DeadBall` (several per frame at low frame rates) can't move it any further.
+pub fn step(ball: &mut BallState, size: Vec2, walls: &[Wall]) -> StepOutcome {
+    if ball.velocity == Vec2::ZERO {
+        return StepOutcome {
+            stopped: true,
+            ..default()
+        };
+    }
+    ball.position = move_ball(ball.position, ball.velocity, DT);
+    let (normals, bounces) = bounce_off_walls(ball.position, size, &mut ball.velocity, walls);
+    let stopped = is_stopped(ball.velocity);
+    if stopped {
+        ball.velocity = Vec2::ZERO;
+    } else {
+        apply_friction(&mut ball.velocity, DT);
+    }
+    StepOutcome {
+        normals,
+        bounces,
+        stopped,
+    }
+}
+
+pub fn move_ball(position: Vec3, velocity: Vec2, delta_seconds: f32) -> Vec3 {
+    position + (velocity * delta_seconds).extend(0.0)
+}
+
+// Reflects the velocity off every wall the ball overlaps, returns the normal of each
+// touched side, pointing away from the wall, and how many times the velocity was
+// reflected. A ball stuck inside a wall gets a zero normal.
+pub fn bounce_off_walls(
+    position: Vec3,
+    size: Vec2,
+    velocity: &mut Vec2,
+    walls: &[Wall],
+) -> (Vec<Vec2>, usize) {
+    let mut normals = Vec::new();
+    let mut bounces = 0;
+    for wall in walls {
+        let Some(collision) = collide(position, size, wall.center, wall.size) else {
+            continue;
+        };
+        normals.push(match collision {
+            Collision::Left => Vec2::NEG_X,
+            Collision::Right => Vec2::X,
+            Collision::Top => Vec2::Y,
+            Collision::Bottom => Vec2::NEG_Y,
+            Collision::Inside => Vec2::ZERO,
+        });
+
+        // only reflect if the ball's velocity is going in the opposite direction of the
+        // collision
+        let mut reflect_x = false;
+        let mut reflect_y = false;
+        match collision {
+            Collision::Left => reflect_x = velocity.x > 0.0,
+            Collision::Right => reflect_x = velocity.x < 0.0,
+            Collision::Top => reflect_y = velocity.y < 0.0,
+            Collision::Bottom => reflect_y = velocity.y > 0.0,
+            Collision::Inside => { /* do nothing */ }
+        }
+
+        if reflect_x {
+            velocity.x = -velocity.x;
+            bounces += 1;
+        }
+        if reflect_y {
+            velocity.y = -velocity.y;
+            bounces += 1;
+        }
+    }
+    (normals, bounces)
+}
+
+// Elastic hit between two balls of the same mass: they trade the parts of their
+// velocities along the line between their centers, so a ball at rest takes all of a
+// head-on hit. Returns the normal pointing from `a` to `b`. Balls only collide while
+// closing in, which lets the balls sharing the tee roll apart untouched.
+pub fn collide_balls(a: &mut BallState, b: &mut BallState, size: Vec2) -> Option<Vec2> {
+    let offset = (b.position - a.position).truncate();
+    let distance = offset.length();
+    let reach = size.x;
+    if distance >= reach || distance == 0.0 {
+        return None;
+    }
+    let normal = offset / distance;
+    let closing_speed = (a.velocity - b.velocity).dot(normal);
+    if closing_speed <= 0.0 {
+        return None;
+    }
+
+    a.velocity -= normal * closing_speed;
+    b.velocity += normal * closing_speed;
+    // Pushed apart, so they never rest inside each other
+    let overlap = (normal * (reach - distance) / 2.0).extend(0.0);
+    a.position -= overlap;
+    b.position += overlap;
+    Some(normal)
+}
+
+pub fn is_stopped(velocity: Vec2) -> bool {
+    velocity.x.abs() <= STOP_SPEED && velocity.y.abs() <= STOP_SPEED
+}
+
+pub fn apply_friction(velocity: &mut Vec2, delta_seconds: f32) {
+    let sum = velocity.x.abs() + velocity.y.abs();
+    if sum > 0.0 {
+        let loss = FRICTION * delta_seconds;
+        velocity.x -= loss * velocity.x.signum() * (velocity.x.abs() / sum);
+        velocity.y -= loss * velocity.y.signum() * (velocity.y.abs() / sum);
+    }
+}
+
+// One tick of every ball on the course: each one moves and bounces off the walls with
+// `step`, then they knock into each other, pairs in the order given. The outcome has
+// the normals of every wall and ball hit, and the wall bounces of all of them.
+pub fn step_balls(balls: &mut [BallState], size: Vec2, walls: &[Wall]) -> StepOutcome {
+    let mut outcome = StepOutcome::default();
+    for ball in balls.iter_mut() {
+        let ball_outcome = step(ball, size, walls);
+        outcome.normals.extend(ball_outcome.normals);
+        outcome.bounces += ball_outcome.bounces;
+    }
+    for second in 1..balls.len() {
+        let (before, after) = balls.split_at_mut(second);
+        for first in before.iter_mut() {
+            outcome
+                .normals
+                .extend(collide_balls(first, &mut after[0], size));
+        }
+    }
+    outcome.stopped = balls.iter().all(|ball| ball.velocity == Vec2::ZERO);
+    outcome
+}
+
+// Upper bound on simulated ticks, a full power shot stops well before this
+pub const MAX_SIMULATION_STEPS: usize = 4096;
+
+// Runs the balls tick by tick with `step_balls`, like the `FixedUpdate` systems, until
+// they all stop or `after_tick` returns false. `after_tick` sees the balls and the
+// outcome of each tick and may move or remove them, like the systems that run after
+// `step_ball`.
+pub fn roll(
+    balls: &mut Vec<BallState>,
+    size: Vec2,
+    walls: &[Wall],
+    mut after_tick: impl FnMut(&mut Vec<BallState>, &StepOutcome) -> bool,
+) {
+    for _ in 0..MAX_SIMULATION_STEPS {
+        let outcome = step_balls(balls, size, walls);
+        if !after_tick(balls, &outcome) || balls.iter().all(|ball| ball.velocity == Vec2::ZERO) {
+            break;
+        }
+    }
+}
+
+// Runs a shot with `roll` and returns the positions the ball goes through. Stops when
+// the ball stops or right after bounce number `max_bounces`.
+pub fn simulate_shot(
+    start: Vec3,
+    size: Vec2,
+    velocity: Vec2,
+    walls: &[Wall],
+    max_bounces: usize,
+) -> Vec<Vec2> {
+    let mut ball = vec![BallState {
+        position: start,
+        velocity,
+    }];
+    let mut bounces = 0;
+    let mut path = vec![start.truncate()];
+    roll(&mut ball, size, walls, |ball, outcome| {
+        path.push(ball[0].position.truncate());
+        bounces += outcome.bounces;
+        outcome.bounces == 0 || bounces < max_bounces
+    });
+    path
+}
@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+use crate::game::components::{Ball, GolfHole};
+use crate::game::levels::BallBundle;
+use crate::game::GameState;
+use crate::locale::Locale;
+use crate::settings::Difficulty;
+
+pub const MAX_PLAYERS: usize = 4;
+
+// Depth between balls waiting on the same spot, so they don't flicker over each other
+const BALL_Z_STEP: f32 = 0.1;
+
+// How many take turns on the course, picked in the main menu
+#[derive(Resource)]
+pub struct Players {
+    pub count: usize,
+    // The last seat is played by the computer at this difficulty. With one player
+    // the computer plays alone, handy to playtest a course.
+    pub computer: Option<Difficulty>,
+}
+
+impl Default for Players {
+    fn default() -> Players {
+        Players {
+            count: 1,
+            computer: None,
+        }
+    }
+}
+
+impl Players {
+    pub fn next(&self) -> usize {
+        self.count % MAX_PLAYERS + 1
+    }
+
+    // Off, then every difficulty from the easiest
+    pub fn next_computer(&self) -> Option<Difficulty> {
+        match self.computer {
+            None => Some(Difficulty::Easy),
+            Some(Difficulty::Hard) => None,
+            Some(difficulty) => Some(difficulty.next()),
+        }
+    }
+
+    pub fn is_computer(&self, player: Player) -> bool {
+        self.computer.is_some() && player.0 + 1 == self.count
+    }
+
+    // What the scoreboard and the leaderboard call a player
+    pub fn name(&self, player: Player, locale: &Locale) -> String {
+        if self.is_computer(player) {
+            locale.get("player.computer").to_string()
+        } else {
+            locale.format("player.name", &[&(player.0 + 1).to_string()])
+        }
+    }
+}
+
+// Whose ball it is, counting from 0
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Player(pub usize);
+
+// The ball the next swing goes to, there is exactly one on the course
+#[derive(Component)]
+pub struct ActiveBall;
+
+// Already in the cup, waiting for the others to finish the hole
+#[derive(Component)]
+pub struct Holed;
+
+// Set by a mulligan, the player who took the swing back plays it again even when
+// another ball is farther from the cup
+#[derive(Resource, Default)]
+pub struct KeepTurn(pub bool);
+
+pub fn single_player(players: Res<Players>) -> bool {
+    players.count == 1 && players.computer.is_none()
+}
+
+// Nobody plays the computer's turn by hand
+pub fn human_turn(players: Res<Players>, ball_query: Query<&Player, With<ActiveBall>>) -> bool {
+    !matches!(ball_query.get_single(), Ok(player) if players.is_computer(*player))
+}
+
+// Every player gets a ball on each hole and the one farthest from the cup plays next
+pub struct PlayersPlugin;
+
+impl Plugin for PlayersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Players>()
+            .init_resource::<KeepTurn>()
+            .add_systems(OnEnter(GameState::Flyover), spawn_player_balls)
+            .add_systems(OnEnter(GameState::DeadBall), pass_turn);
+    }
+}
+
+// Levels place a single ball, it becomes the first player's and the others start
+// on the same spot
+#[allow(clippy::type_complexity)]
+pub fn spawn_player_balls(
+    mut commands: Commands,
+    players: Res<Players>,
+    ball_query: Query<(Entity, &Transform), (With<Ball>, Without<Player>)>,
+) {
+    let Ok((entity, transform)) = ball_query.get_single() else {
+        return;
+    };
+    commands.entity(entity).insert((Player(0), ActiveBall));
+    for player in 1..players.count {
+        let translation = transform.translation - Vec3::Z * BALL_Z_STEP * player as f32;
+        commands.spawn((BallBundle::new(translation, Vec2::ZERO), Player(player)));
+    }
+}
+
+// Farthest from the cup first, ties go to the lower player
+pub fn next_player(balls: &[(Player, Vec2)], cup: Vec2) -> Option<Player> {
+    let mut order = balls.to_vec();
+    order.sort_by_key(|(player, _)| player.0);
+    order
+        .into_iter()
+        .fold(
+            None,
+            |farthest: Option<(Player, f32)>, (player, position)| {
+                let distance = position.distance(cup);
+                match farthest {
+                    Some((_, farthest_distance)) if farthest_distance >= distance => farthest,
+                    _ => Some((player, distance)),
+                }
+            },
+        )
+        .map(|(player, _)| player)
+}
+
+#[allow(clippy::type_complexity)]
+pub fn pass_turn(
+    mut commands: Commands,
+    ball_query: Query<(Entity, &Transform, &Player, Has<ActiveBall>), (With<Ball>, Without<Holed>)>,
+    hole_query: Query<&Transform, With<GolfHole>>,
+    mut keep_turn: ResMut<KeepTurn>,
+) {
+    if std::mem::take(&mut keep_turn.0) {
+        return;
+    }
+    let Ok(hole_transform) = hole_query.get_single() else {
+        return;
+    };
+    let balls: Vec<(Player, Vec2)> = ball_query
+        .iter()
+        .map(|(_, transform, player, _)| (*player, transform.translation.truncate()))
+        .collect();
+    let Some(next) = next_player(&balls, hole_transform.translation.truncate()) else {
+        return;
+    };
+    for (entity, _, player, active) in ball_query.iter() {
+        if *player == next && !active {
+            commands.entity(entity).insert(ActiveBall);
+        } else if *player != next && active {
+            commands.entity(entity).remove::<ActiveBall>();
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use std::fs;
+
+use crate::config;
+use crate::game::aiming::{swing_ball, Swing};
+use crate::game::components::{Ball, Velocity};
+use crate::game::levels::{Course, Level};
+use crate::game::players::{ActiveBall, Holed, Player, Players};
+use crate::game::swings_count::{insert_score, Scoreboard};
+use crate::game::GameState;
+use crate::settings::Difficulty;
+use crate::AppState;
+
+pub mod viewer;
+
+// One file per round, numbered so the newest sorts last
+const REPLAYS_DIRECTORY: &str = "config/replays";
+// Older rounds are deleted as new ones are saved
+pub const KEPT_REPLAYS: usize = 20;
+
+// A swing as the simulation took it. Physics is deterministic, so this is all it
+// takes to roll the ball the same way again.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Shot {
+    // Whose ball took it, older replays only had one
+    #[serde(default)]
+    pub player: usize,
+    pub hole: i32,
+    pub start: Vec2,
+    pub velocity: Vec2,
+    // Physics ticks since the round started
+    pub tick: u64,
+    // The other balls still in play, by player. A mulligan can put balls back that
+    // the swing it takes back had knocked away.
+    #[serde(default)]
+    pub others: Vec<(usize, Vec2)>,
+}
+
+// Every swing of a round in order, mulligans included. Holds the round being
+// played, then stays around to be watched.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Replay {
+    pub players: usize,
+    // Only names the last seat, its swings are recorded like anyone's
+    pub computer: Option<Difficulty>,
+    // An endless course builds the same holes again from its seed
+    pub course: Course,
+    pub shots: Vec<Shot>,
+}
+
+impl Default for Replay {
+    fn default() -> Replay {
+        Replay {
+            players: 1,
+            computer: None,
+            course: Course::Classic,
+            shots: Vec::new(),
+        }
+    }
+}
+
+impl Replay {
+    // Replays on disk, oldest first
+    fn numbers() -> Vec<u32> {
+        let Ok(entries) = fs::read_dir(REPLAYS_DIRECTORY) else {
+            return Vec::new();
+        };
+        let mut numbers: Vec<u32> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                name.strip_prefix("round-")?
+                    .strip_suffix(".ron")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        numbers.sort_unstable();
+        numbers
+    }
+
+    fn path(number: u32) -> String {
+        format!("{}/round-{:04}.ron", REPLAYS_DIRECTORY, number)
+    }
+
+    pub fn load_latest() -> Replay {
+        Replay::numbers()
+            .last()
+            .and_then(|number| config::load(&Replay::path(*number)))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let number = Replay::numbers().last().map_or(1, |number| number + 1);
+        config::save_compact(&Replay::path(number), self);
+        Replay::prune();
+    }
+
+    // Keeps only the newest `KEPT_REPLAYS` rounds
+    fn prune() {
+        let numbers = Replay::numbers();
+        let stale = numbers.len().saturating_sub(KEPT_REPLAYS);
+        for number in &numbers[..stale] {
+            let path = Replay::path(*number);
+            if let Err(error) = fs::remove_file(&path) {
+                warn!("Could not delete {}: {}", path, error);
+            }
+        }
+    }
+}
+
+// Physics ticks since the round, or the replay, started
+#[derive(Resource, Default)]
+pub struct RoundTick(pub u64);
+
+// Where the replay is at
+#[derive(Resource, Default)]
+pub struct Playback {
+    // The shot waiting to be taken once the ball rests
+    pub next_shot: usize,
+    // Set to play again from that shot
+    pub jump: Option<usize>,
+}
+
+impl Playback {
+    // The shot on screen, the last one taken
+    pub fn current_shot(&self) -> usize {
+        self.next_shot.saturating_sub(1)
+    }
+}
+
+// Records the round while it is played and plays it back in `AppState::Replay`,
+// taking the recorded swings through the same `Swing` events and physics
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Replay>()
+            .init_resource::<RoundTick>()
+            .init_resource::<Playback>()
+            .add_systems(OnEnter(AppState::Game), start_recording)
+            .add_systems(
+                FixedUpdate,
+                count_round_ticks
+                    .run_if(in_state(AppState::Game).or_else(in_state(AppState::Replay))),
+            )
+            .add_systems(
+                Update,
+                record_shots
+                    .before(swing_ball)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(GameState::DeadBall)),
+            )
+            .add_systems(
+                OnEnter(AppState::Replay),
+                (start_playback, insert_score).chain(),
+            )
+            .add_systems(
+                Update,
+                play_shots
+                    .before(swing_ball)
+                    .run_if(in_state(AppState::Replay)),
+            )
+            .add_systems(OnExit(AppState::Replay), remove_replay_score);
+    }
+}
+
+pub fn start_recording(
+    players: Res<Players>,
+    course: Res<Course>,
+    mut replay: ResMut<Replay>,
+    mut round_tick: ResMut<RoundTick>,
+) {
+    replay.players = players.count;
+    replay.computer = players.computer;
+    replay.course = *course;
+    replay.shots.clear();
+    round_tick.0 = 0;
+}
+
+pub fn count_round_ticks(mut round_tick: ResMut<RoundTick>) {
+    round_tick.0 += 1;
+}
+
+#[allow(clippy::type_complexity)]
+pub fn record_shots(
+    mut swing_events: EventReader<Swing>,
+    ball_query: Query<(&Transform, &Player), With<ActiveBall>>,
+    others_query: Query<(&Transform, &Player), (With<Ball>, Without<ActiveBall>, Without<Holed>)>,
+    level: Res<Level>,
+    round_tick: Res<RoundTick>,
+    mut replay: ResMut<Replay>,
+) {
+    // `swing_ball` only takes the last swing of a frame
+    let Some(swing) = swing_events.read().last() else {
+        return;
+    };
+    let Ok((ball_transform, player)) = ball_query.get_single() else {
+        return;
+    };
+    let mut others: Vec<(usize, Vec2)> = others_query
+        .iter()
+        .map(|(transform, player)| (player.0, transform.translation.truncate()))
+        .collect();
+    others.sort_by_key(|(player, _)| *player);
+    replay.shots.push(Shot {
+        player: player.0,
+        hole: level.0,
+        start: ball_transform.translation.truncate(),
+        velocity: swing.velocity,
+        tick: round_tick.0,
+        others,
+    });
+}
+
+pub fn start_playback(
+    replay: Res<Replay>,
+    mut players: ResMut<Players>,
+    mut course: ResMut<Course>,
+    mut playback: ResMut<Playback>,
+    mut round_tick: ResMut<RoundTick>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    if replay.shots.is_empty() {
+        app_state_next_state.set(AppState::MainMenu);
+        return;
+    }
+    players.count = replay.players.max(1);
+    players.computer = replay.computer;
+    *course = replay.course;
+    *playback = Playback {
+        next_shot: 0,
+        jump: Some(0),
+    };
+    round_tick.0 = 0;
+}
+
+pub fn remove_replay_score(mut commands: Commands) {
+    commands.remove_resource::<Scoreboard>();
+}
+
+// Loads the hole of the next shot and takes it once the ball rests and the round
+// clock reaches the tick it was taken at
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn play_shots(
+    mut commands: Commands,
+    replay: Res<Replay>,
+    mut playback: ResMut<Playback>,
+    mut round_tick: ResMut<RoundTick>,
+    mut level: ResMut<Level>,
+    game_state: Res<State<GameState>>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+    mut ball_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &mut Visibility,
+            &Player,
+            Has<ActiveBall>,
+        ),
+        With<Ball>,
+    >,
+    mut swing_events: EventWriter<Swing>,
+) {
+    if let Some(shot_index) = playback.jump.take() {
+        let Some(shot) = replay.shots.get(shot_index) else {
+            return;
+        };
+        playback.next_shot = shot_index;
+        round_tick.0 = shot.tick;
+        level.0 = shot.hole;
+        game_state_next_state.set(GameState::RestartingHole);
+        return;
+    }
+
+    if game_state.get() != &GameState::DeadBall {
+        return;
+    }
+    let Some(shot) = replay.shots.get(playback.next_shot) else {
+        return;
+    };
+    // Sinking the ball loads the next hole, anything else in the replay loads here
+    if shot.hole != level.0 {
+        level.0 = shot.hole;
+        game_state_next_state.set(GameState::RestartingHole);
+        return;
+    }
+    if round_tick.0 < shot.tick {
+        return;
+    }
+
+    // Balls go back to where they were when the shot was taken, out of the cup after
+    // a mulligan, and whoever took it gets the turn
+    let mut shooter_active = None;
+    for (entity, mut transform, mut velocity, mut visibility, player, active) in
+        ball_query.iter_mut()
+    {
+        let shooter = player.0 == shot.player;
+        let position = if shooter {
+            Some(shot.start)
+        } else {
+            shot.others
+                .iter()
+                .find(|(other, _)| *other == player.0)
+                .map(|(_, position)| *position)
+        };
+        if let Some(position) = position {
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+            velocity.0 = Vec2::ZERO;
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Holed>();
+        }
+        if shooter {
+            shooter_active = Some(active);
+            commands.entity(entity).insert(ActiveBall);
+        } else if active {
+            commands.entity(entity).remove::<ActiveBall>();
+        }
+    }
+    // `swing_ball` finds a ball that just got the turn on the next frame
+    if shooter_active != Some(true) {
+        return;
+    }
+    swing_events.send(Swing {
+        velocity: shot.velocity,
+    });
+    // Waits between shots are kept, whatever a flyover took this time
+    round_tick.0 = shot.tick;
+    playback.next_shot += 1;
+}
@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState, Binding, Bindings};
+use crate::game::levels::Level;
+use crate::game::replay::{Playback, Replay};
+use crate::locale::Locale;
+use crate::settings::Settings;
+use crate::AppState;
+
+// Playback speeds to step through, 1x is the default
+const SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+const NORMAL_SPEED: usize = 2;
+
+const HUD_FONT_SIZE: f32 = 30.0;
+const HUD_PADDING: Val = Val::Px(5.0);
+const HUD_COLOR: Color = Color::rgb(0.5, 0.5, 1.0);
+const HINT_COLOR: Color = Color::rgb(0.8, 0.8, 0.8);
+
+#[derive(Resource)]
+pub struct PlaybackSpeed(pub usize);
+
+impl Default for PlaybackSpeed {
+    fn default() -> PlaybackSpeed {
+        PlaybackSpeed(NORMAL_SPEED)
+    }
+}
+
+#[derive(Component)]
+pub struct ReplayHud;
+
+// Saves every round and lets the player watch the last one. Playback reuses the
+// round's actions: pause toggles play, power up/down changes speed and aim
+// left/right jumps between strokes.
+pub struct ReplayViewerPlugin;
+
+impl Plugin for ReplayViewerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Replay::load_latest())
+            .init_resource::<PlaybackSpeed>()
+            .add_systems(OnExit(AppState::Game), save_replay)
+            .add_systems(OnEnter(AppState::Replay), (enter_replay, spawn_replay_hud))
+            .add_systems(
+                Update,
+                (control_playback, update_replay_hud)
+                    .chain()
+                    .run_if(in_state(AppState::Replay)),
+            )
+            .add_systems(
+                OnExit(AppState::Replay),
+                (despawn_replay_hud, reset_playback_time),
+            );
+    }
+}
+
+pub fn save_replay(replay: Res<Replay>) {
+    if !replay.shots.is_empty() {
+        replay.save();
+    }
+}
+
+pub fn enter_replay(
+    mut backgroung_color: ResMut<ClearColor>,
+    mut window_query: Query<&mut Window>,
+    settings: Res<Settings>,
+    mut playback_speed: ResMut<PlaybackSpeed>,
+) {
+    backgroung_color.0 = settings.palette().course;
+    window_query.single_mut().cursor.visible = true;
+    *playback_speed = PlaybackSpeed::default();
+}
+
+pub fn control_playback(
+    action_state: Res<ActionState>,
+    replay: Res<Replay>,
+    mut playback: ResMut<Playback>,
+    mut playback_speed: ResMut<PlaybackSpeed>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if action_state.just_pressed(Action::Pause) {
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
+        }
+    }
+
+    if action_state.just_pressed(Action::PowerUp) {
+        playback_speed.0 = (playback_speed.0 + 1).min(SPEEDS.len() - 1);
+    }
+    if action_state.just_pressed(Action::PowerDown) {
+        playback_speed.0 = playback_speed.0.saturating_sub(1);
+    }
+    time.set_relative_speed(SPEEDS[playback_speed.0]);
+
+    let current_shot = playback.current_shot();
+    if action_state.just_pressed(Action::AimLeft) {
+        playback.jump = Some(current_shot.saturating_sub(1));
+    }
+    if action_state.just_pressed(Action::AimRight) && current_shot + 1 < replay.shots.len() {
+        playback.jump = Some(current_shot + 1);
+    }
+}
+
+// Keyboard keys read best in a hint, whatever the action is also bound to
+fn binding_label(bindings: &Bindings, action: Action, locale: &Locale) -> String {
+    let bound = bindings.get(action);
+    bound
+        .iter()
+        .find(|binding| matches!(binding, Binding::Key(_)))
+        .or(bound.first())
+        .map_or_else(|| "-".to_string(), |binding| binding.label(locale))
+}
+
+pub fn spawn_replay_hud(mut commands: Commands, locale: Res<Locale>, bindings: Res<Bindings>) {
+    let controls = locale.format(
+        "replay.controls",
+        &[
+            &binding_label(&bindings, Action::Pause, &locale),
+            &binding_label(&bindings, Action::PowerDown, &locale),
+            &binding_label(&bindings, Action::PowerUp, &locale),
+            &binding_label(&bindings, Action::AimLeft, &locale),
+            &binding_label(&bindings, Action::AimRight, &locale),
+        ],
+    );
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::from_style(TextStyle {
+                font_size: HUD_FONT_SIZE,
+                color: HUD_COLOR,
+                ..default()
+            }),
+            TextSection::new(
+                format!("\n{}", controls),
+                TextStyle {
+                    font_size: HUD_FONT_SIZE * 0.7,
+                    color: HINT_COLOR,
+                    ..default()
+                },
+            ),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: HUD_PADDING,
+            left: HUD_PADDING,
+            ..default()
+        }),
+        ReplayHud,
+    ));
+}
+
+pub fn update_replay_hud(
+    replay: Res<Replay>,
+    playback: Res<Playback>,
+    playback_speed: Res<PlaybackSpeed>,
+    level: Res<Level>,
+    time: Res<Time<Virtual>>,
+    locale: Res<Locale>,
+    mut hud_query: Query<&mut Text, With<ReplayHud>>,
+) {
+    let mut status = locale.format(
+        "replay.status",
+        &[
+            &playback.next_shot.to_string(),
+            &replay.shots.len().to_string(),
+            &level.0.to_string(),
+            &SPEEDS[playback_speed.0].to_string(),
+        ],
+    );
+    if time.is_paused() {
+        status.push_str(locale.get("replay.paused"));
+    }
+    for mut text in hud_query.iter_mut() {
+        text.sections[0].value = status.clone();
+    }
+}
+
+pub fn despawn_replay_hud(mut commands: Commands, hud_query: Query<Entity, With<ReplayHud>>) {
+    for entity in hud_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn reset_playback_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+    time.set_relative_speed(1.0);
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState};
+use crate::game::components::*;
+use crate::game::levels::Level;
+use crate::game::players::{ActiveBall, Holed, KeepTurn, Player};
+use crate::game::swings_count::Scoreboard;
+use crate::game::{GameState, PauseState};
+use crate::net::offline;
+use crate::AppState;
+
+// The course right before a swing
+#[derive(Clone, Debug)]
+pub struct SwingStart {
+    // The ball that took the swing
+    pub ball: Entity,
+    // Every ball still in play, balls it knocks away go back too
+    pub positions: Vec<(Entity, Vec3)>,
+}
+
+// How the course was before the last swing, used to take a mulligan
+#[derive(Resource, Default)]
+pub struct LastSwing(pub Option<SwingStart>);
+
+pub struct RetryPlugin;
+
+impl Plugin for RetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (restart_hole, take_mulligan)
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(GameState::DeadBall).or_else(in_state(GameState::BallMoving)))
+                .run_if(in_state(PauseState::Running))
+                .run_if(offline),
+        );
+    }
+}
+
+pub fn clear_last_swing(mut last_swing: ResMut<LastSwing>) {
+    last_swing.0 = None;
+}
+
+pub fn restart_hole(
+    action_state: Res<ActionState>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+) {
+    if action_state.just_pressed(Action::Restart) {
+        game_state_next_state.set(GameState::RestartingHole);
+    }
+}
+
+// Everyone plays the hole again
+pub fn reset_hole_score(mut scoreboard: ResMut<Scoreboard>, level: Res<Level>) {
+    for card in scoreboard.cards.iter_mut() {
+        let hole_strokes = card.hole_mut(level.0).strokes;
+        card.score -= hole_strokes;
+        card.hole_mut(level.0).strokes = 0;
+    }
+}
+
+pub fn reload_map(mut game_state_next_state: ResMut<NextState<GameState>>) {
+    game_state_next_state.set(GameState::LoadingMap);
+}
+
+// Takes back the last swing, whoever took it, and gives them the turn again, see
+// `KeepTurn`
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn take_mulligan(
+    mut commands: Commands,
+    action_state: Res<ActionState>,
+    mut last_swing: ResMut<LastSwing>,
+    mut scoreboard: ResMut<Scoreboard>,
+    level: Res<Level>,
+    mut ball_query: Query<(&mut Transform, &mut Velocity, &mut Visibility, &Player), With<Ball>>,
+    active_query: Query<Entity, With<ActiveBall>>,
+    game_state: Res<State<GameState>>,
+    mut keep_turn: ResMut<KeepTurn>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+) {
+    if !action_state.just_pressed(Action::Mulligan) {
+        return;
+    }
+    let Some(start) = last_swing.0.as_ref() else {
+        return;
+    };
+    let Ok((_, _, _, player)) = ball_query.get(start.ball) else {
+        return;
+    };
+    let card = scoreboard.card_mut(*player);
+    if card.mulligans_left == 0 {
+        return;
+    }
+    let Some(start) = last_swing.0.take() else {
+        return;
+    };
+
+    for (entity, position) in start.positions {
+        let Ok((mut transform, mut velocity, mut visibility, _)) = ball_query.get_mut(entity)
+        else {
+            continue;
+        };
+        transform.translation = position;
+        velocity.0 = Vec2::ZERO;
+        *visibility = Visibility::Inherited;
+        commands.entity(entity).remove::<Holed>();
+    }
+
+    card.score -= 1;
+    card.mulligans_left -= 1;
+    let hole = card.hole_mut(level.0);
+    hole.strokes -= 1;
+    hole.mulligans += 1;
+
+    for active in active_query.iter() {
+        commands.entity(active).remove::<ActiveBall>();
+    }
+    commands.entity(start.ball).insert(ActiveBall);
+    // Only a rolling ball goes through `OnEnter(GameState::DeadBall)` and `pass_turn`
+    // again, a dead one already has its turn back
+    keep_turn.0 = *game_state.get() == GameState::BallMoving;
+    game_state_next_state.set(GameState::DeadBall);
+}
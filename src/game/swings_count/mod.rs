@@ -1,16 +1,75 @@
-use bevy::{prelude::*, transform::commands};
+use bevy::prelude::*;
 
-use crate::AppState;
-use crate::GameOver;
+use crate::game::players::{Player, Players};
+use crate::locale::Locale;
+use crate::{AppState, FinalScore, GameOver};
 
+// Number of strokes a player may take back during a single round
+pub const MULLIGANS_PER_ROUND: usize = 3;
+
+#[derive(Clone, Copy, Default)]
+pub struct HoleCard {
+    pub strokes: usize,
+    pub mulligans: usize,
+}
+
+// One player's round
+#[derive(Clone)]
+pub struct Scorecard {
+    pub score: usize,
+    pub holes: Vec<HoleCard>,
+    pub mulligans_left: usize,
+}
+
+impl Default for Scorecard {
+    fn default() -> Scorecard {
+        Scorecard {
+            score: 0,
+            holes: Vec::new(),
+            mulligans_left: MULLIGANS_PER_ROUND,
+        }
+    }
+}
+
+impl Scorecard {
+    // Levels start at 1, so hole `level` lives at index `level - 1`
+    pub fn hole_mut(&mut self, level: i32) -> &mut HoleCard {
+        let index = (level - 1).max(0) as usize;
+        if self.holes.len() <= index {
+            self.holes.resize(index + 1, HoleCard::default());
+        }
+        &mut self.holes[index]
+    }
+
+    pub fn strokes(&self, level: i32) -> usize {
+        let index = (level - 1).max(0) as usize;
+        self.holes.get(index).map_or(0, |hole| hole.strokes)
+    }
+
+    pub fn mulligans_used(&self) -> usize {
+        self.holes.iter().map(|hole| hole.mulligans).sum()
+    }
+}
+
+// A card for every player, indexed by `Player`
 #[derive(Resource)]
 pub struct Scoreboard {
-    pub score: usize,
+    pub cards: Vec<Scorecard>,
 }
 
-impl Default for Scoreboard {
-    fn default() -> Scoreboard {
-        Scoreboard {score: 0}
+impl Scoreboard {
+    pub fn new(players: usize) -> Scoreboard {
+        Scoreboard {
+            cards: vec![Scorecard::default(); players.max(1)],
+        }
+    }
+
+    pub fn card(&self, player: Player) -> &Scorecard {
+        &self.cards[player.0]
+    }
+
+    pub fn card_mut(&mut self, player: Player) -> &mut Scorecard {
+        &mut self.cards[player.0]
     }
 }
 
@@ -27,19 +86,36 @@ pub struct SwingsPlugins;
 
 impl Plugin for SwingsPlugins {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Game), insert_score)
-            .add_systems(OnEnter(AppState::Game), spawn_swings)
-            .add_systems(OnExit(AppState::Game), despawn_swings)
-            .add_systems(OnExit(AppState::Game), remove_score);
+        app.add_systems(OnEnter(AppState::Game), spawn_swings)
+            .add_systems(
+                Update,
+                update_swings_labels
+                    .run_if(in_state(AppState::Game))
+                    .run_if(resource_changed::<Locale>()),
+            )
+            .add_systems(OnExit(AppState::Game), despawn_swings);
     }
 }
 
-fn spawn_swings(mut commands: Commands) {
+fn spawn_swings(mut commands: Commands, locale: Res<Locale>) {
     // Scoreboard
     commands.spawn((
         TextBundle::from_sections([
             TextSection::new(
-                "Swings: ",
+                locale.get("hud.swings"),
+                TextStyle {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ),
+            TextSection::from_style(TextStyle {
+                font_size: SCOREBOARD_FONT_SIZE,
+                color: SCORE_COLOR,
+                ..default()
+            }),
+            TextSection::new(
+                locale.get("hud.mulligans"),
                 TextStyle {
                     font_size: SCOREBOARD_FONT_SIZE,
                     color: TEXT_COLOR,
@@ -51,6 +127,18 @@ fn spawn_swings(mut commands: Commands) {
                 color: SCORE_COLOR,
                 ..default()
             }),
+            // Whose turn it is, left empty with a single player
+            TextSection::from_style(TextStyle {
+                font_size: SCOREBOARD_FONT_SIZE,
+                color: TEXT_COLOR,
+                ..default()
+            }),
+            // Hole and par on an endless course
+            TextSection::from_style(TextStyle {
+                font_size: SCOREBOARD_FONT_SIZE,
+                color: TEXT_COLOR,
+                ..default()
+            }),
         ])
         .with_style(Style {
             position_type: PositionType::Absolute,
@@ -62,14 +150,22 @@ fn spawn_swings(mut commands: Commands) {
     ));
 }
 
-fn despawn_swings(mut commands: Commands, swings_query: Query<Entity, With<Text>>) {
+// The language can change from the pause menu, mid round
+fn update_swings_labels(locale: Res<Locale>, mut swings_query: Query<&mut Text, With<Seksu>>) {
+    for mut text in swings_query.iter_mut() {
+        text.sections[0].value = locale.get("hud.swings").to_string();
+        text.sections[2].value = locale.get("hud.mulligans").to_string();
+    }
+}
+
+fn despawn_swings(mut commands: Commands, swings_query: Query<Entity, With<Seksu>>) {
     if !swings_query.is_empty() {
         commands.entity(swings_query.single()).despawn();
     }
 }
 
-pub fn insert_score(mut commands: Commands) {
-    commands.insert_resource(Scoreboard::default())
+pub fn insert_score(mut commands: Commands, players: Res<Players>) {
+    commands.insert_resource(Scoreboard::new(players.count))
 }
 
 pub fn remove_score(
@@ -77,6 +173,17 @@ pub fn remove_score(
     mut game_over_event_writer: EventWriter<GameOver>,
     scoreboard: Res<Scoreboard>,
 ) {
-    game_over_event_writer.send(GameOver {final_score: scoreboard.score});
+    game_over_event_writer.send(GameOver {
+        scores: scoreboard
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(player, card)| FinalScore {
+                player,
+                score: card.score,
+                mulligans: card.mulligans_used(),
+            })
+            .collect(),
+    });
     commands.remove_resource::<Scoreboard>();
 }
@@ -1,36 +1,35 @@
-use bevy::{
-    prelude::*,
-    sprite::collide_aabb::{collide, Collision},
-    sprite::MaterialMesh2dBundle,
-    transform::commands,
-};
+use bevy::prelude::*;
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
 
 use super::components::*;
-use crate::game::levels::Level;
+use super::physics::{self, Wall};
+use crate::game::levels::{Course, HolePar, Level, LAST_LEVEL};
+use crate::game::players::{ActiveBall, Holed, Player, Players};
+use crate::game::retry::LastSwing;
 use crate::game::swings_count::Scoreboard;
 use crate::game::swings_count::Seksu;
 use crate::game::GameState;
-use crate::AppState::GameOver;
+use crate::locale::Locale;
+use crate::settings::{AimingMode, Settings};
 use crate::*;
 // use crate::game::levels::*;
 
 const VELOCITY_VECTOR_QUERY: Color = Color::DARK_GRAY;
 const VELOCITY_VECTOR_QUAD_SIZE: Vec3 = Vec3::new(4.0, 100.0, 0.0);
 const VELOCITY_VECTOR_TRIANGLE_SIZE: Vec3 = Vec3::new(25.0, 25.0, 0.0);
-const VELOCITY_VECTOR_SIZE: f32 = 275.;
-const VELOCITY_FACTOR: f32 = 3.5;
 
-const GOLF_COURSE_COLOR: Color = Color::rgb(0.0, 0.533333, 0.329412);
+pub fn start_round(mut next_app_state: ResMut<NextState<GameState>>) {
+    next_app_state.set(GameState::LoadingMap);
+}
 
 pub fn enter_game_state(
-    mut next_app_state: ResMut<NextState<GameState>>,
     mut backgroung_color: ResMut<ClearColor>,
     mut window_query: Query<&mut Window>,
+    settings: Res<Settings>,
 ) {
-    backgroung_color.0 = GOLF_COURSE_COLOR;
-    window_query.single_mut().cursor.visible = false;
-    next_app_state.set(GameState::LoadingMap);
-    println!("Entered GameState::LoadingMap");
+    backgroung_color.0 = settings.palette().course;
+    // Only the slingshot needs the cursor, in the other modes the aim arrow follows it
+    window_query.single_mut().cursor.visible = settings.aiming_mode == AimingMode::Slingshot;
 }
 
 // pub fn exit_game_state(mut next_app_state: ResMut<NextState<GameState>>) {
@@ -38,10 +37,71 @@ pub fn enter_game_state(
 //     println!("Entered GameState::OutOfGame");
 // }
 
-pub fn apply_velocity(mut query: Query<(&mut Transform, &Velocity)>, time: Res<Time>) {
-    for (mut transform, velocity) in &mut query {
-        transform.translation.x += velocity.x * time.delta_seconds();
-        transform.translation.y += velocity.y * time.delta_seconds();
+pub fn add_ball_and_hole_meshes(
+    mut commands: Commands,
+    ball_query: Query<(Entity, Option<&Player>), Added<Ball>>,
+    hole_query: Query<Entity, Added<GolfHole>>,
+    settings: Res<Settings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let palette = settings.palette();
+    for (entity, player) in ball_query.iter() {
+        let player = player.copied().unwrap_or_default();
+        commands.entity(entity).insert((
+            Mesh2dHandle(meshes.add(shape::Circle::default().into())),
+            materials.add(ColorMaterial::from(palette.ball_of(player.0))),
+        ));
+    }
+    for entity in hole_query.iter() {
+        commands.entity(entity).insert((
+            Mesh2dHandle(meshes.add(shape::Circle::default().into())),
+            materials.add(ColorMaterial::from(palette.cup)),
+        ));
+    }
+}
+
+// Levels spawn with the default colors, this repaints new entities and, when the
+// colourblind setting changes, everything on the course
+#[allow(clippy::type_complexity)]
+pub fn apply_palette(
+    settings: Res<Settings>,
+    mut backgroung_color: ResMut<ClearColor>,
+    mut wall_query: Query<(&mut Sprite, Ref<Collider>), Without<Water>>,
+    mut water_query: Query<(&mut Sprite, Ref<Water>), Without<Collider>>,
+    ball_query: Query<(&Handle<ColorMaterial>, Ref<Ball>, Option<&Player>)>,
+    hole_query: Query<(&Handle<ColorMaterial>, Ref<GolfHole>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let repaint_all = settings.is_changed();
+    let palette = settings.palette();
+
+    if repaint_all {
+        backgroung_color.0 = palette.course;
+    }
+    for (mut sprite, collider) in wall_query.iter_mut() {
+        if repaint_all || collider.is_added() {
+            sprite.color = palette.wall;
+        }
+    }
+    for (mut sprite, water) in water_query.iter_mut() {
+        if repaint_all || water.is_added() {
+            sprite.color = palette.water;
+        }
+    }
+    for (material, ball, player) in ball_query.iter() {
+        if repaint_all || ball.is_added() {
+            if let Some(material) = materials.get_mut(material) {
+                material.color = palette.ball_of(player.copied().unwrap_or_default().0);
+            }
+        }
+    }
+    for (material, hole) in hole_query.iter() {
+        if repaint_all || hole.is_added() {
+            if let Some(material) = materials.get_mut(material) {
+                material.color = palette.cup;
+            }
+        }
     }
 }
 
@@ -76,118 +136,147 @@ pub fn spawn_velocity_vector(
     ));
 }
 
-pub fn update_scoreboard(scoreboard: Res<Scoreboard>, mut query: Query<&mut Text, With<Seksu>>) {
-    let mut text = query.single_mut();
-    text.sections[1].value = scoreboard.score.to_string();
+// Shows the card of whoever plays next
+pub fn update_scoreboard(
+    scoreboard: Res<Scoreboard>,
+    players: Res<Players>,
+    locale: Res<Locale>,
+    level: Res<Level>,
+    hole_par: Res<HolePar>,
+    ball_query: Query<&Player, With<ActiveBall>>,
+    mut query: Query<&mut Text, With<Seksu>>,
+) {
+    let Ok(mut text) = query.get_single_mut() else {
+        return;
+    };
+    let player = ball_query.get_single().copied().unwrap_or_default();
+    let card = scoreboard.card(player);
+    text.sections[1].value = card.score.to_string();
+    text.sections[3].value = card.mulligans_left.to_string();
+    text.sections[4].value = if players.count > 1 || players.is_computer(player) {
+        format!("   {}", players.name(player, &locale))
+    } else {
+        String::new()
+    };
+    // The swings count is the score of the whole run
+    text.sections[5].value = match hole_par.0 {
+        Some(par) => locale.format(
+            "hud.endless_hole",
+            &[&level.0.to_string(), &par.to_string()],
+        ),
+        None => String::new(),
+    };
 }
 
-pub fn set_ball_velocity(
-    mouse_input: Res<Input<MouseButton>>,
-    mut ball_query: Query<(&mut Velocity, &Transform), With<Ball>>,
-    mut app_state_next_state: ResMut<NextState<GameState>>,
-    mut window_query: Query<&mut Window>,
-    mut vector_quad_query: Query<
-        &mut Transform,
-        (
-            With<VelocityVectorQuad>,
-            Without<Ball>,
-            Without<VelocityVectorTriangle>,
-        ),
-    >,
-    mut vector_triangle_query: Query<
-        &mut Transform,
-        (
-            With<VelocityVectorTriangle>,
-            Without<Ball>,
-            Without<VelocityVectorQuad>,
-        ),
-    >,
-    mut scoreboard: ResMut<Scoreboard>,
+pub fn unspawn_velocity_vector(
+    mut commands: Commands,
+    vector_query: Query<Entity, With<VelocityVector>>,
 ) {
-    // Calculate ball velocity vector
-    let (mut ball_velocity, ball_transform) = ball_query.single_mut();
-    let cursor_position = window_query
-        .single_mut()
-        .cursor_position()
-        .unwrap_or(Vec2::new(0.0, 0.0));
-
-    let ball_position = ball_transform.translation;
-
-    let mut ball_velocity_vector = Vec2::new(
-        cursor_position.x - (ball_position.x + window_query.single_mut().width() / 2.0),
-        -cursor_position.y + (window_query.single_mut().height() / 2.0 - ball_position.y),
-    );
-
-    let mut vector_quad = vector_quad_query.single_mut();
-    let mut vector_triangle = vector_triangle_query.single_mut();
-
-    let hipotenusa = ball_velocity_vector.length();
-
-    if hipotenusa > VELOCITY_VECTOR_SIZE {
-        ball_velocity_vector.x = VELOCITY_VECTOR_SIZE * (ball_velocity_vector.x / hipotenusa);
-        ball_velocity_vector.y = VELOCITY_VECTOR_SIZE * (ball_velocity_vector.y / hipotenusa);
+    for entity in vector_query.iter() {
+        commands.entity(entity).despawn();
     }
+}
 
-    // Rotate vector arrow
-    vector_quad.translation.x = ball_velocity_vector.x / 2.0 + ball_position.x;
-    vector_quad.translation.y = ball_velocity_vector.y / 2.0 + ball_position.y;
-    vector_quad.scale.y = ball_velocity_vector.length();
-
-    vector_triangle.translation.x = ball_velocity_vector.x + ball_position.x;
-    vector_triangle.translation.y = ball_velocity_vector.y + ball_position.y;
+// The ball drops in once its center is this close to the hole's center
+pub fn hole_capture_radius() -> f32 {
+    GOLF_HOLE_SIZE.length() / 2.5
+}
 
-    let vector_orient = PI / 2.0;
-    let mut angle_to_target = ball_velocity_vector.y.atan2(ball_velocity_vector.x);
-    if angle_to_target < 0. {
-        angle_to_target += 2.0 * PI;
-    }
-    let angle_to_rotate = angle_to_target - vector_orient;
-    vector_quad.rotation = Quat::from_rotation_z(angle_to_rotate);
-    vector_triangle.rotation = Quat::from_rotation_z(angle_to_rotate);
+// Only the course plane counts, the ball and the hole sit at different depths
+pub fn ball_in_hole(ball_position: Vec3, hole_position: Vec3) -> bool {
+    ball_position.truncate().distance(hole_position.truncate()) <= hole_capture_radius()
+}
 
-    ball_velocity_vector.x *= VELOCITY_FACTOR;
-    ball_velocity_vector.y *= VELOCITY_FACTOR;
+// Only the center counts, a ball can roll along the edge of a pond
+pub fn ball_in_water(ball_position: Vec3, water: &[Rect]) -> bool {
+    water
+        .iter()
+        .any(|pond| pond.contains(ball_position.truncate()))
+}
 
-    if mouse_input.pressed(MouseButton::Left) {
-        scoreboard.score += 1;
-        *ball_velocity = Velocity(ball_velocity_vector);
-        app_state_next_state.set(GameState::BallMoving);
-        println!("Entered AppState::BallMoving");
-    }
+pub fn pond_from_transform(transform: &Transform) -> Rect {
+    Rect::from_center_size(transform.translation.truncate(), transform.scale.truncate())
 }
 
-pub fn unspawn_velocity_vector(
-    mut commands: Commands,
-    vector_query: Query<Entity, With<VelocityVector>>,
+// A ball in the water goes back to where it was before the last swing, stopped, and
+// its player takes a penalty stroke
+#[allow(clippy::type_complexity)]
+pub fn check_ball_in_water(
+    mut ball_query: Query<
+        (Entity, &mut Transform, &mut Velocity, &Player),
+        (With<Ball>, Without<Holed>),
+    >,
+    water_query: Query<&Transform, (With<Water>, Without<Ball>)>,
+    last_swing: Res<LastSwing>,
+    level: Res<Level>,
+    mut scoreboard: ResMut<Scoreboard>,
 ) {
-    for entity in vector_query.iter() {
-        commands.entity(entity).despawn();
+    let water: Vec<Rect> = water_query.iter().map(pond_from_transform).collect();
+    let Some(start) = last_swing.0.as_ref() else {
+        return;
+    };
+    for (entity, mut transform, mut velocity, player) in ball_query.iter_mut() {
+        if !ball_in_water(transform.translation, &water) {
+            continue;
+        }
+        let Some((_, position)) = start.positions.iter().find(|(ball, _)| *ball == entity) else {
+            continue;
+        };
+        transform.translation = *position;
+        velocity.0 = Vec2::ZERO;
+        let card = scoreboard.card_mut(*player);
+        card.score += 1;
+        card.hole_mut(level.0).strokes += 1;
     }
 }
 
+// Any ball can drop in, also one knocked in by another player. The hole is over once
+// every ball is in, until then a holed ball waits out of sight.
+#[allow(clippy::type_complexity)]
 pub fn check_ball_inside_hole(
-    ball_query: Query<&Transform, With<Ball>>,
+    mut commands: Commands,
+    mut ball_query: Query<
+        (Entity, &Transform, &mut Velocity, &mut Visibility),
+        (With<Ball>, Without<Holed>),
+    >,
     hole_query: Query<&Transform, With<GolfHole>>,
     mut app_state_next_state: ResMut<NextState<GameState>>,
 ) {
-    let ball_position = ball_query.single().translation;
     let hole_position = hole_query.single().translation;
-    let distance = ball_position.distance(hole_position);
+    let (holed, left): (Vec<_>, Vec<_>) = ball_query
+        .iter_mut()
+        .partition(|(_, transform, _, _)| ball_in_hole(transform.translation, hole_position));
 
-    if distance <= GOLF_HOLE_SIZE.length() / 2.5 {
+    if holed.is_empty() {
+        return;
+    }
+    if left.is_empty() {
         app_state_next_state.set(GameState::UnloadingMap);
-        println!("Entered AppState::UnloadingMap");
+        return;
+    }
+    for (entity, _, mut velocity, mut visibility) in holed {
+        velocity.0 = Vec2::ZERO;
+        *visibility = Visibility::Hidden;
+        commands.entity(entity).insert(Holed).remove::<ActiveBall>();
+    }
+    // Balls it knocked away still have to stop
+    if left
+        .iter()
+        .all(|(_, _, velocity, _)| velocity.0 == Vec2::ZERO)
+    {
+        app_state_next_state.set(GameState::DeadBall);
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn unload_map(
     mut commands: Commands,
     ball_query: Query<Entity, With<Ball>>,
     golf_hole_query: Query<Entity, With<GolfHole>>,
-    wall_query: Query<Entity, With<Collider>>,
+    wall_query: Query<Entity, Or<(With<Collider>, With<Water>)>>,
 ) {
-    if !ball_query.is_empty() {
-        commands.entity(ball_query.single()).despawn();
+    for entity in ball_query.iter() {
+        commands.entity(entity).despawn();
     }
     if !golf_hole_query.is_empty() {
         commands.entity(golf_hole_query.single()).despawn();
@@ -200,18 +289,21 @@ pub fn unload_map(
 
 pub fn set_load_map_state(
     mut game_state_next_state: ResMut<NextState<GameState>>,
+    app_state: Res<State<AppState>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
     mut level_resource: ResMut<Level>,
+    course: Res<Course>,
 ) {
-    if level_resource.0 == 3 {
-        app_state_next_state.set(AppState::GameOver);
+    // An endless course never runs out of holes
+    if level_resource.0 == LAST_LEVEL && *course == Course::Classic {
+        // A replay just stops on the empty course
+        if app_state.get() == &AppState::Game {
+            app_state_next_state.set(AppState::GameOver);
+        }
         game_state_next_state.set(GameState::OutOfGame);
-        println!("Entered AppState::GameOver");
-        println!("Entered GameState::OutOfGame");
     } else {
         level_resource.0 += 1;
         game_state_next_state.set(GameState::LoadingMap);
-        println!("Entered GameState::LoadingMap");
     }
 }
 
@@ -221,61 +313,54 @@ pub fn reset_load_map_state (
     level_resource.0 = 1;
 }
 
-pub fn uptade_ball_velocity(
-    mut ball_query: Query<(&mut Velocity, &Transform), With<Ball>>,
-    collider_query: Query<(Entity, &Transform), With<Collider>>,
+// Advances every ball on the course one fixed tick, always by `physics::DT` whatever
+// the clock says. Balls move in player order and then hit each other, so a tick never
+// depends on the order entities are stored in. Nothing rolls anymore once the last
+// ball stops.
+#[allow(clippy::type_complexity)]
+pub fn step_ball(
+    mut ball_query: Query<
+        (&mut Transform, &mut Velocity, Option<&Player>),
+        (With<Ball>, Without<Holed>),
+    >,
+    collider_query: Query<&Transform, (With<Collider>, Without<Ball>)>,
     mut collision_events: EventWriter<CollisionEvent>,
     mut app_state_next_state: ResMut<NextState<GameState>>,
 ) {
-    let (mut ball_velocity, ball_transform) = ball_query.single_mut();
-    let ball_size = ball_transform.scale.truncate();
-
-    // check collision with walls
-    for (collider_entity, transform) in &collider_query {
-        let collision = collide(
-            ball_transform.translation,
-            ball_size,
-            transform.translation,
-            transform.scale.truncate(),
-        );
-        if let Some(collision) = collision {
-            // Sends a collision event so that other systems can react to the collision
-            collision_events.send_default();
-
-            // reflect the ball when it collides
-            let mut reflect_x = false;
-            let mut reflect_y = false;
-
-            // only reflect if the ball's velocity is going in the opposite direction of the
-            // collision
-            match collision {
-                Collision::Left => reflect_x = ball_velocity.x > 0.0,
-                Collision::Right => reflect_x = ball_velocity.x < 0.0,
-                Collision::Top => reflect_y = ball_velocity.y < 0.0,
-                Collision::Bottom => reflect_y = ball_velocity.y > 0.0,
-                Collision::Inside => { /* do nothing */ }
-            }
-
-            // reflect velocity on the x-axis if we hit something on the x-axis
-            if reflect_x {
-                ball_velocity.x = -ball_velocity.x;
-            }
-
-            // reflect velocity on the y-axis if we hit something on the y-axis
-            if reflect_y {
-                ball_velocity.y = -ball_velocity.y;
-            }
-        }
+    let walls = physics::sorted_walls(collider_query.iter().map(wall_from_transform));
+    let mut balls: Vec<_> = ball_query.iter_mut().collect();
+    balls.sort_by_key(|(_, _, player)| player.copied().unwrap_or_default().0);
+    let mut states: Vec<physics::BallState> = balls
+        .iter()
+        .map(|(transform, velocity, _)| physics::BallState {
+            position: transform.translation,
+            velocity: velocity.0,
+        })
+        .collect();
+    let Some(size) = balls
+        .first()
+        .map(|(transform, _, _)| transform.scale.truncate())
+    else {
+        return;
+    };
+
+    // Sends a collision event so that other systems can react to the collision
+    for normal in physics::step_balls(&mut states, size, &walls).normals {
+        collision_events.send(CollisionEvent { normal });
     }
 
-    if ball_velocity.x.abs() <= 5.0 && ball_velocity.y.abs() <= 5.0 {
+    for ((transform, velocity, _), ball) in balls.iter_mut().zip(states.iter()) {
+        transform.translation = ball.position;
+        velocity.0 = ball.velocity;
+    }
+    if states.iter().all(|ball| ball.velocity == Vec2::ZERO) {
         app_state_next_state.set(GameState::DeadBall);
-        println!("Entered AppState::DeadBall");
-    } else {
-        let sum = ball_velocity.x.abs() + ball_velocity.y.abs();
-        if sum >= 0.0 {
-            ball_velocity.x -= 2.75 * ball_velocity.x.signum() * (ball_velocity.x.abs() / sum);
-            ball_velocity.y -= 2.75 * ball_velocity.y.signum() * (ball_velocity.y.abs() / sum);
-        }
+    }
+}
+
+pub fn wall_from_transform(transform: &Transform) -> Wall {
+    Wall {
+        center: transform.translation,
+        size: transform.scale.truncate(),
     }
 }
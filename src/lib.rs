@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+pub mod actions;
+use crate::actions::{Action, ActionState};
+
+mod config;
+
+pub mod locale;
+
+pub mod camera;
+
+pub mod debug;
+
+pub mod game;
+use crate::game::*;
+
+pub mod ui;
+
+pub mod net;
+
+pub mod settings;
+
+// Size of the visible course in world units, whatever the window size
+pub const RESOLUTION: Vec2 = Vec2::new(1920.0, 1080.0);
+
+// We set the z-value of the ball to 1 so it renders on top in the case of overlapping sprites.
+pub const BALL_SIZE: Vec3 = Vec3::new(15.0, 15.0, 0.0);
+
+// We set the z-value of the ball to 1 so it renders on top in the case of overlapping sprites.
+pub const GOLF_HOLE_SIZE: Vec3 = Vec3::new(25.0, 25.0, 0.0);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Game,
+    GameOver,
+    // Watching the last round again
+    Replay,
+}
+
+// The course is on screen while playing and while watching a replay
+pub fn course_shown(app_state: Res<State<AppState>>) -> bool {
+    matches!(app_state.get(), AppState::Game | AppState::Replay)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FinalScore {
+    pub player: usize,
+    pub score: usize,
+    pub mulligans: usize,
+}
+
+// Every player's result, in player order
+#[derive(Event)]
+pub struct GameOver {
+    pub scores: Vec<FinalScore>,
+}
+
+pub fn transition_to_main_menu_state(
+    action_state: Res<ActionState>,
+    app_state: Res<State<AppState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if action_state.just_pressed(Action::MainMenu) && app_state.get() != &AppState::MainMenu {
+        next_game_state.set(GameState::OutOfGame);
+        next_app_state.set(AppState::MainMenu);
+    }
+}
+
+#[cfg(debug_assertions)]
+pub fn transition_to_game_over_menu_state(
+    keyboard_input: Res<Input<KeyCode>>,
+    app_state: Res<State<AppState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::G) && app_state.get() != &AppState::GameOver {
+        next_game_state.set(GameState::OutOfGame);
+        next_app_state.set(AppState::GameOver);
+    }
+}
+
+pub fn exit_game_state(
+    mut backgroung_color: ResMut<ClearColor>,
+    mut window_query: Query<&mut Window>,
+){
+    backgroung_color.0 = Color::rgb_u8(43, 44, 47);
+    window_query.single_mut().cursor.visible = true;
+}
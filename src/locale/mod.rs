@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+use crate::settings::Settings;
+
+// The string tables ship inside the binary, so a missing file can't leave the menus blank
+const EN_US: &str = include_str!("../../assets/locales/en-US.ron");
+const PT_BR: &str = include_str!("../../assets/locales/pt-BR.ron");
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    EnUs,
+    PtBr,
+}
+
+impl Language {
+    pub fn next(self) -> Language {
+        match self {
+            Language::EnUs => Language::PtBr,
+            Language::PtBr => Language::EnUs,
+        }
+    }
+
+    // Each language is named in itself, so it can be found from any other
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::EnUs => "English",
+            Language::PtBr => "Português",
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            Language::EnUs => EN_US,
+            Language::PtBr => PT_BR,
+        }
+    }
+}
+
+// Every piece of UI text, looked up by key in the current language
+#[derive(Resource)]
+pub struct Locale {
+    pub language: Language,
+    strings: HashMap<String, String>,
+}
+
+impl Locale {
+    pub fn new(language: Language) -> Locale {
+        let strings = match ron::from_str(language.table()) {
+            Ok(strings) => strings,
+            Err(error) => {
+                warn!("Ignoring the {:?} string table: {}", language, error);
+                HashMap::new()
+            }
+        };
+        Locale { language, strings }
+    }
+
+    // Missing keys show up as the key itself, which is easy to spot on screen
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map(String::as_str).unwrap_or(key)
+    }
+
+    // Fills each `{}` in the string with the next argument
+    pub fn format(&self, key: &str, arguments: &[&str]) -> String {
+        let mut parts = self.get(key).split("{}");
+        let mut text = parts.next().unwrap_or_default().to_string();
+        for (index, part) in parts.enumerate() {
+            text.push_str(arguments.get(index).copied().unwrap_or_default());
+            text.push_str(part);
+        }
+        text
+    }
+}
+
+impl FromWorld for Locale {
+    fn from_world(world: &mut World) -> Locale {
+        let language = world
+            .get_resource::<Settings>()
+            .map(|settings| settings.language)
+            .unwrap_or_default();
+        Locale::new(language)
+    }
+}
+
+// Text whose first section is the string under `key`, kept in sync with the language
+#[derive(Component)]
+pub struct Localized {
+    pub key: &'static str,
+}
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Locale>().add_systems(
+            Update,
+            (
+                switch_language.run_if(resource_changed::<Settings>()),
+                update_localized_text.run_if(resource_changed::<Locale>()),
+            )
+                .chain(),
+        );
+    }
+}
+
+pub fn switch_language(settings: Res<Settings>, mut locale: ResMut<Locale>) {
+    if locale.language != settings.language {
+        *locale = Locale::new(settings.language);
+    }
+}
+
+pub fn update_localized_text(locale: Res<Locale>, mut text_query: Query<(&mut Text, &Localized)>) {
+    for (mut text, localized) in text_query.iter_mut() {
+        text.sections[0].value = locale.get(localized.key).to_string();
+    }
+}
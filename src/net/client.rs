@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+
+use std::collections::BTreeMap;
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+
+use crate::net::protocol::{decode, encode, ClientMessage, NetShot, RelayMessage, MAX_DATAGRAM};
+
+// How often unanswered messages go out again, UDP drops what it likes
+pub const RESEND_SECONDS: f64 = 0.25;
+// How long the relay may stay quiet before we give up on it. It answers every
+// resend, so this is many lost datagrams in a row.
+pub const TIMEOUT_SECONDS: f64 = 5.0;
+
+// Our side of an online round: joins the relay, sends our shots until they come back
+// and hands out everyone's shots in the relay's order
+pub struct NetClient {
+    socket: UdpSocket,
+    relay: SocketAddr,
+    // Our seat, once the relay gave us one
+    pub player: Option<usize>,
+    // Known once everyone joined
+    pub players: Option<usize>,
+    // Order of the next shot to play
+    next_order: u32,
+    received: BTreeMap<u32, NetShot>,
+    next_seq: u32,
+    // Our shots the relay hasn't echoed back yet
+    unconfirmed: Vec<ClientMessage>,
+    last_sent: Option<f64>,
+    // Last time anything arrived from the relay
+    last_heard: Option<f64>,
+    pub full: bool,
+}
+
+impl NetClient {
+    pub fn connect(relay: SocketAddr) -> io::Result<NetClient> {
+        let local: SocketAddr = if relay.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.set_nonblocking(true)?;
+        Ok(NetClient {
+            socket,
+            relay,
+            player: None,
+            players: None,
+            next_order: 0,
+            received: BTreeMap::new(),
+            next_seq: 0,
+            unconfirmed: Vec::new(),
+            last_sent: None,
+            last_heard: None,
+            full: false,
+        })
+    }
+
+    pub fn started(&self) -> bool {
+        self.player.is_some() && self.players.is_some()
+    }
+
+    // The relay answered once and then stopped answering. Before it first answers
+    // it may just not be up yet, so we keep waiting.
+    pub fn lost(&self, now: f64) -> bool {
+        self.last_heard
+            .is_some_and(|last_heard| now - last_heard > TIMEOUT_SECONDS)
+    }
+
+    // Reads everything that arrived and, every `RESEND_SECONDS`, asks again for
+    // what is still missing. `now` is any clock in seconds.
+    pub fn poll(&mut self, now: f64) {
+        let mut buffer = [0; MAX_DATAGRAM];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((length, from)) if from == self.relay => {
+                    if let Some(message) = decode(&buffer[..length]) {
+                        self.last_heard = Some(now);
+                        self.receive(message);
+                    }
+                }
+                Ok(_) => {}
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                // The relay isn't up yet, keep trying
+                Err(_) => break,
+            }
+        }
+
+        if self
+            .last_sent
+            .is_some_and(|last_sent| now - last_sent < RESEND_SECONDS)
+        {
+            return;
+        }
+        self.last_sent = Some(now);
+        if !self.started() {
+            self.send(&ClientMessage::Join);
+            return;
+        }
+        self.send(&ClientMessage::Resend {
+            next: self.next_order,
+        });
+        for message in self.unconfirmed.clone() {
+            self.send(&message);
+        }
+    }
+
+    fn receive(&mut self, message: RelayMessage) {
+        match message {
+            RelayMessage::Welcome { player } => self.player = Some(player),
+            RelayMessage::Start { players } => self.players = Some(players),
+            RelayMessage::Full => self.full = true,
+            RelayMessage::Alive => {}
+            RelayMessage::Shot(shot) => {
+                if Some(shot.player) == self.player {
+                    self.unconfirmed.retain(|message| {
+                        !matches!(message, ClientMessage::Shot { seq, .. } if *seq == shot.seq)
+                    });
+                }
+                if shot.order >= self.next_order {
+                    self.received.insert(shot.order, shot);
+                }
+            }
+        }
+    }
+
+    fn send(&self, message: &ClientMessage) {
+        let _ = self.socket.send_to(&encode(message), self.relay);
+    }
+
+    // Our own swing, already taken here
+    pub fn send_shot(&mut self, hole: i32, velocity: Vec2) {
+        let message = ClientMessage::Shot {
+            seq: self.next_seq,
+            hole,
+            velocity,
+        };
+        self.next_seq += 1;
+        self.send(&message);
+        self.unconfirmed.push(message);
+    }
+
+    // The shot everyone plays next, once it arrived
+    pub fn next_shot(&self) -> Option<NetShot> {
+        self.received.get(&self.next_order).copied()
+    }
+
+    pub fn take_shot(&mut self) -> Option<NetShot> {
+        let shot = self.received.remove(&self.next_order)?;
+        self.next_order += 1;
+        Some(shot)
+    }
+}
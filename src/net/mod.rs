@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+
+use std::net::SocketAddr;
+
+use crate::game::aiming::{swing_ball, Swing};
+use crate::game::levels::{Course, Level};
+use crate::game::players::{ActiveBall, Player, Players};
+use crate::game::GameState;
+use crate::AppState;
+
+pub mod client;
+pub mod protocol;
+pub mod relay;
+
+use client::NetClient;
+
+// Where `relay` listens unless told otherwise
+pub const DEFAULT_RELAY: &str = "127.0.0.1:7777";
+
+#[derive(Resource)]
+pub struct NetSession {
+    pub client: NetClient,
+    // The round was started, it only starts once per session
+    pub started: bool,
+}
+
+// Left behind when the relay stopped answering, so the main menu can say why the
+// round ended
+#[derive(Resource)]
+pub struct Disconnected;
+
+// Online rounds: every machine plays the whole course and only the shots go over the
+// wire. Our swings are sent to the relay, the others' come back from it and are
+// taken as `Swing`s once their ball is up.
+pub struct NetPlugin {
+    // Set by `--connect`, the game stays offline without it
+    pub relay: Option<SocketAddr>,
+}
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(relay) = self.relay {
+            match NetClient::connect(relay) {
+                Ok(client) => {
+                    info!("Joining the relay at {}", relay);
+                    app.insert_resource(NetSession {
+                        client,
+                        started: false,
+                    });
+                }
+                Err(error) => warn!("Playing offline, could not open a socket: {}", error),
+            }
+        }
+        app.add_systems(
+            Update,
+            (
+                poll_relay,
+                drop_lost_session,
+                start_online_round.run_if(in_state(AppState::MainMenu)),
+            )
+                .chain()
+                .run_if(resource_exists::<NetSession>()),
+        )
+        .add_systems(
+            Update,
+            (
+                play_remote_shots.before(swing_ball),
+                send_local_shots.after(swing_ball),
+            )
+                .run_if(resource_exists::<NetSession>())
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(GameState::DeadBall)),
+        )
+        .add_systems(OnExit(AppState::MainMenu), clear_disconnected);
+    }
+}
+
+// True when nobody else decides the next swing, always the case offline
+pub fn local_turn(
+    session: Option<Res<NetSession>>,
+    ball_query: Query<&Player, With<ActiveBall>>,
+) -> bool {
+    let Some(session) = session else {
+        return true;
+    };
+    match ball_query.get_single() {
+        Ok(player) => session.client.player == Some(player.0),
+        Err(_) => false,
+    }
+}
+
+// Restarts and mulligans would need everyone to agree, so they are offline only
+pub fn offline(session: Option<Res<NetSession>>) -> bool {
+    session.is_none()
+}
+
+pub fn poll_relay(mut session: ResMut<NetSession>, time: Res<Time<Real>>) {
+    session.client.poll(time.elapsed_seconds_f64());
+}
+
+// Without the relay nobody's shots arrive anymore, so the session ends and
+// everyone goes back to the main menu to play offline
+pub fn drop_lost_session(
+    mut commands: Commands,
+    session: Res<NetSession>,
+    time: Res<Time<Real>>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    if !session.client.lost(time.elapsed_seconds_f64()) {
+        return;
+    }
+    warn!("Lost the relay, back to the main menu");
+    commands.remove_resource::<NetSession>();
+    commands.insert_resource(Disconnected);
+    game_state_next_state.set(GameState::OutOfGame);
+    app_state_next_state.set(AppState::MainMenu);
+}
+
+pub fn clear_disconnected(mut commands: Commands) {
+    commands.remove_resource::<Disconnected>();
+}
+
+// Everyone is in, so every machine starts the same round
+pub fn start_online_round(
+    mut session: ResMut<NetSession>,
+    mut players: ResMut<Players>,
+    mut course: ResMut<Course>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    if session.started || !session.client.started() {
+        return;
+    }
+    let Some(count) = session.client.players else {
+        return;
+    };
+    session.started = true;
+    players.count = count;
+    // Every seat online is someone at a machine
+    players.computer = None;
+    // The relay doesn't pass endless seeds around, online rounds play the built-in holes
+    *course = Course::Classic;
+    app_state_next_state.set(AppState::Game);
+}
+
+// Our swings were taken already, the relay only has to pass them on
+pub fn send_local_shots(
+    mut swing_events: EventReader<Swing>,
+    mut session: ResMut<NetSession>,
+    ball_query: Query<&Player, With<ActiveBall>>,
+    level: Res<Level>,
+) {
+    let Some(swing) = swing_events.read().last() else {
+        return;
+    };
+    let Ok(player) = ball_query.get_single() else {
+        return;
+    };
+    if session.client.player == Some(player.0) {
+        session.client.send_shot(level.0, swing.velocity);
+    }
+}
+
+// Takes the next shot in the relay's order once its ball has the turn. Our own shots
+// come back too and are only skipped.
+pub fn play_remote_shots(
+    mut session: ResMut<NetSession>,
+    ball_query: Query<&Player, With<ActiveBall>>,
+    level: Res<Level>,
+    mut swing_events: EventWriter<Swing>,
+) {
+    let Some(shot) = session.client.next_shot() else {
+        return;
+    };
+    if session.client.player == Some(shot.player) {
+        session.client.take_shot();
+        return;
+    }
+    let Ok(player) = ball_query.get_single() else {
+        return;
+    };
+    if shot.hole != level.0 || shot.player != player.0 {
+        return;
+    }
+    swing_events.send(Swing {
+        velocity: shot.velocity,
+    });
+    session.client.take_shot();
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+// Online rounds only ever exchange shot inputs, every machine simulates them with the
+// same deterministic physics. Each message is one datagram of ron text.
+
+// Big enough for any message, they are all a few dozen bytes
+pub const MAX_DATAGRAM: usize = 1024;
+
+// A swing as the relay hands it out, numbered in the order everyone plays it
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NetShot {
+    pub order: u32,
+    pub player: usize,
+    // Counts the shots of one player, so a resent shot is only taken once
+    pub seq: u32,
+    pub hole: i32,
+    pub velocity: Vec2,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    // Sent until the round starts, the relay answers with `Welcome`
+    Join,
+    Shot { seq: u32, hole: i32, velocity: Vec2 },
+    // Asks again for every shot from `next` on, also tells the relay we're still here
+    Resend { next: u32 },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RelayMessage {
+    Welcome { player: usize },
+    // Everyone joined, the round can start
+    Start { players: usize },
+    // No seat left for another player
+    Full,
+    Shot(NetShot),
+    // Answers a `Resend` with nothing to resend, so quiet rounds still show the
+    // relay is up
+    Alive,
+}
+
+pub fn encode<T: Serialize>(message: &T) -> Vec<u8> {
+    ron::ser::to_string(message)
+        .map(String::into_bytes)
+        .unwrap_or_default()
+}
+
+// Anything that doesn't parse is dropped, like a lost datagram
+pub fn decode<T: DeserializeOwned>(datagram: &[u8]) -> Option<T> {
+    let text = std::str::from_utf8(datagram).ok()?;
+    ron::from_str(text).ok()
+}
@@ -0,0 +1,143 @@
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+
+use crate::net::protocol::{decode, encode, ClientMessage, NetShot, RelayMessage, MAX_DATAGRAM};
+
+// Seats players in the order they join and puts their shots in one order for
+// everyone. It knows nothing about the course, whose turn it is is up to the clients.
+pub struct Relay {
+    players: usize,
+    clients: Vec<SocketAddr>,
+    shots: Vec<NetShot>,
+    // Next shot expected from each player
+    next_seq: Vec<u32>,
+}
+
+impl Relay {
+    pub fn new(players: usize) -> Relay {
+        let players = players.max(1);
+        Relay {
+            players,
+            clients: Vec::new(),
+            shots: Vec::new(),
+            next_seq: vec![0; players],
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.clients.len() == self.players
+    }
+
+    pub fn shots(&self) -> &[NetShot] {
+        &self.shots
+    }
+
+    // What to send, and to whom, in answer to a message
+    pub fn handle(
+        &mut self,
+        from: SocketAddr,
+        message: ClientMessage,
+    ) -> Vec<(SocketAddr, RelayMessage)> {
+        let player = self.clients.iter().position(|client| *client == from);
+        match (message, player) {
+            (ClientMessage::Join, Some(player)) => self.welcome(from, player),
+            (ClientMessage::Join, None) if !self.is_full() => {
+                self.clients.push(from);
+                let player = self.clients.len() - 1;
+                if self.is_full() {
+                    let mut replies = vec![(from, RelayMessage::Welcome { player })];
+                    replies.extend(self.broadcast(RelayMessage::Start {
+                        players: self.players,
+                    }));
+                    replies
+                } else {
+                    self.welcome(from, player)
+                }
+            }
+            (ClientMessage::Join, None) => vec![(from, RelayMessage::Full)],
+            (
+                ClientMessage::Shot {
+                    seq,
+                    hole,
+                    velocity,
+                },
+                Some(player),
+            ) => {
+                if seq == self.next_seq[player] {
+                    let shot = NetShot {
+                        order: self.shots.len() as u32,
+                        player,
+                        seq,
+                        hole,
+                        velocity,
+                    };
+                    self.shots.push(shot);
+                    self.next_seq[player] += 1;
+                    self.broadcast(RelayMessage::Shot(shot))
+                } else {
+                    // Already taken, the echo got lost on the way back
+                    self.shots
+                        .iter()
+                        .filter(|shot| shot.player == player && shot.seq == seq)
+                        .map(|shot| (from, RelayMessage::Shot(*shot)))
+                        .collect()
+                }
+            }
+            (ClientMessage::Resend { next }, Some(_)) => {
+                let replies: Vec<(SocketAddr, RelayMessage)> = self
+                    .shots
+                    .iter()
+                    .skip(next as usize)
+                    .map(|shot| (from, RelayMessage::Shot(*shot)))
+                    .collect();
+                if replies.is_empty() {
+                    vec![(from, RelayMessage::Alive)]
+                } else {
+                    replies
+                }
+            }
+            // Strangers only get to join
+            (_, None) => Vec::new(),
+        }
+    }
+
+    fn welcome(&self, to: SocketAddr, player: usize) -> Vec<(SocketAddr, RelayMessage)> {
+        let mut replies = vec![(to, RelayMessage::Welcome { player })];
+        if self.is_full() {
+            replies.push((
+                to,
+                RelayMessage::Start {
+                    players: self.players,
+                },
+            ));
+        }
+        replies
+    }
+
+    fn broadcast(&self, message: RelayMessage) -> Vec<(SocketAddr, RelayMessage)> {
+        self.clients
+            .iter()
+            .map(|client| (*client, message.clone()))
+            .collect()
+    }
+}
+
+// Answers datagrams on `socket` until it can't receive anymore. A client going away
+// only costs the datagrams sent to it.
+pub fn serve(socket: UdpSocket, players: usize) -> io::Result<()> {
+    let mut relay = Relay::new(players);
+    let mut buffer = [0; MAX_DATAGRAM];
+    loop {
+        let (length, from) = match socket.recv_from(&mut buffer) {
+            Ok(received) => received,
+            Err(error) if error.kind() == io::ErrorKind::ConnectionReset => continue,
+            Err(error) => return Err(error),
+        };
+        let Some(message) = decode::<ClientMessage>(&buffer[..length]) else {
+            continue;
+        };
+        for (to, reply) in relay.handle(from, message) {
+            let _ = socket.send_to(&encode(&reply), to);
+        }
+    }
+}
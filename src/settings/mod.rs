@@ -0,0 +1,381 @@
+use bevy::audio::{GlobalVolume, Volume, VolumeLevel};
+use bevy::prelude::*;
+use bevy::window::{PresentMode, PrimaryWindow, WindowMode as BevyWindowMode};
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::locale::Language;
+
+const SETTINGS_PATH: &str = "config/settings.ron";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum AimingMode {
+    // Aim from the ball towards the cursor and click anywhere to shoot
+    #[default]
+    Pointer,
+    // Press near the ball and drag backwards, release to shoot
+    Slingshot,
+    // Click to lock direction, then click again while the power bar oscillates
+    PowerMeter,
+}
+
+impl AimingMode {
+    pub fn next(self) -> AimingMode {
+        match self {
+            AimingMode::Pointer => AimingMode::Slingshot,
+            AimingMode::Slingshot => AimingMode::PowerMeter,
+            AimingMode::PowerMeter => AimingMode::Pointer,
+        }
+    }
+
+    // Key of the name shown in the settings menu
+    pub fn key(self) -> &'static str {
+        match self {
+            AimingMode::Pointer => "aiming.pointer",
+            AimingMode::Slingshot => "aiming.slingshot",
+            AimingMode::PowerMeter => "aiming.power_meter",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn next(self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    // Key of the name shown in the settings menu
+    pub fn key(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+        }
+    }
+
+    // Wall bounces the trajectory preview shows, none means no preview at all
+    pub fn preview_bounces(self) -> Option<usize> {
+        match self {
+            Difficulty::Easy => Some(3),
+            Difficulty::Normal => Some(1),
+            Difficulty::Hard => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum WindowMode {
+    #[default]
+    Windowed,
+    Fullscreen,
+    Borderless,
+}
+
+impl WindowMode {
+    pub fn next(self) -> WindowMode {
+        match self {
+            WindowMode::Windowed => WindowMode::Fullscreen,
+            WindowMode::Fullscreen => WindowMode::Borderless,
+            WindowMode::Borderless => WindowMode::Windowed,
+        }
+    }
+
+    // Key of the name shown in the settings menu
+    pub fn key(self) -> &'static str {
+        match self {
+            WindowMode::Windowed => "window.windowed",
+            WindowMode::Fullscreen => "window.fullscreen",
+            WindowMode::Borderless => "window.borderless",
+        }
+    }
+}
+
+// The volume settings, and the component that puts a sound under one of them.
+// Master scales every sound, the others only their own.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VolumeChannel {
+    Master,
+    Music,
+    Effects,
+}
+
+impl VolumeChannel {
+    pub const ALL: [VolumeChannel; 3] = [
+        VolumeChannel::Master,
+        VolumeChannel::Music,
+        VolumeChannel::Effects,
+    ];
+
+    // Key of the label shown in the settings menu
+    pub fn key(self) -> &'static str {
+        match self {
+            VolumeChannel::Master => "settings.volume",
+            VolumeChannel::Music => "settings.music_volume",
+            VolumeChannel::Effects => "settings.effects_volume",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum ColorblindMode {
+    #[default]
+    Off,
+    // Protanopia and deuteranopia
+    RedGreen,
+    // Tritanopia
+    BlueYellow,
+}
+
+impl ColorblindMode {
+    pub fn next(self) -> ColorblindMode {
+        match self {
+            ColorblindMode::Off => ColorblindMode::RedGreen,
+            ColorblindMode::RedGreen => ColorblindMode::BlueYellow,
+            ColorblindMode::BlueYellow => ColorblindMode::Off,
+        }
+    }
+
+    // Key of the name shown in the settings menu
+    pub fn key(self) -> &'static str {
+        match self {
+            ColorblindMode::Off => "colorblind.off",
+            ColorblindMode::RedGreen => "colorblind.red_green",
+            ColorblindMode::BlueYellow => "colorblind.blue_yellow",
+        }
+    }
+
+    // Colors picked from the Okabe-Ito palette so they stay apart for each kind of colourblindness
+    pub fn palette(self) -> Palette {
+        match self {
+            ColorblindMode::Off => Palette {
+                course: Color::rgb(0.0, 0.533333, 0.329412),
+                wall: Color::rgb(0.8, 0.8, 0.8),
+                ball: Color::WHITE,
+                other_balls: [Color::GOLD, Color::RED, Color::rgb(0.5, 0.8, 1.0)],
+                cup: Color::BLACK,
+                water: Color::rgb(0.1, 0.35, 0.8),
+                power_fill: Color::rgb(1.0, 0.5, 0.5),
+                accuracy_center: Color::rgb(0.5, 0.5, 1.0),
+            },
+            ColorblindMode::RedGreen => Palette {
+                course: Color::rgb(0.0, 0.447, 0.698),
+                wall: Color::rgb(0.941, 0.894, 0.259),
+                ball: Color::WHITE,
+                other_balls: [
+                    Color::rgb(0.902, 0.624, 0.0),
+                    Color::rgb(0.8, 0.475, 0.655),
+                    Color::rgb(0.337, 0.706, 0.914),
+                ],
+                cup: Color::BLACK,
+                water: Color::rgb(0.0, 0.15, 0.35),
+                power_fill: Color::rgb(0.902, 0.624, 0.0),
+                accuracy_center: Color::rgb(0.337, 0.706, 0.914),
+            },
+            // Blue against yellow and green against blue blur together, red against
+            // cyan doesn't, so the walls are reddish purple around a bluish green course
+            ColorblindMode::BlueYellow => Palette {
+                course: Color::rgb(0.0, 0.62, 0.451),
+                wall: Color::rgb(0.8, 0.475, 0.655),
+                ball: Color::WHITE,
+                other_balls: [
+                    Color::rgb(0.835, 0.369, 0.0),
+                    Color::rgb(0.941, 0.894, 0.259),
+                    Color::rgb(0.337, 0.706, 0.914),
+                ],
+                cup: Color::BLACK,
+                water: Color::rgb(0.0, 0.2, 0.4),
+                power_fill: Color::rgb(0.835, 0.369, 0.0),
+                accuracy_center: Color::rgb(0.8, 0.475, 0.655),
+            },
+        }
+    }
+}
+
+// Colors of everything on the course that carries meaning
+#[derive(Clone, Copy, Debug)]
+pub struct Palette {
+    pub course: Color,
+    pub wall: Color,
+    pub ball: Color,
+    // Balls of the second to fourth player in hot seat
+    pub other_balls: [Color; 3],
+    pub cup: Color,
+    pub water: Color,
+    pub power_fill: Color,
+    pub accuracy_center: Color,
+}
+
+impl Palette {
+    pub fn ball_of(&self, player: usize) -> Color {
+        match player {
+            0 => self.ball,
+            _ => self.other_balls[(player - 1) % self.other_balls.len()],
+        }
+    }
+}
+
+// Anything missing from the file keeps its default
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub aiming_mode: AimingMode,
+    // Adds a third click to the power meter that locks shot accuracy
+    pub accuracy_click: bool,
+    pub difficulty: Difficulty,
+    // Corner map on holes bigger than the screen
+    pub show_minimap: bool,
+    // Translucent ball replaying the best attempt at the hole
+    pub show_ghost: bool,
+    pub window_mode: WindowMode,
+    pub vsync: bool,
+    // From 0 to 100, in steps of 10
+    pub master_volume: u32,
+    pub music_volume: u32,
+    pub effects_volume: u32,
+    pub colorblind_mode: ColorblindMode,
+    pub language: Language,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            aiming_mode: AimingMode::default(),
+            accuracy_click: false,
+            difficulty: Difficulty::default(),
+            show_minimap: true,
+            show_ghost: true,
+            window_mode: WindowMode::default(),
+            vsync: true,
+            master_volume: 100,
+            music_volume: 100,
+            effects_volume: 100,
+            colorblind_mode: ColorblindMode::default(),
+            language: Language::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Settings {
+        config::load(SETTINGS_PATH).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        config::save(SETTINGS_PATH, self);
+    }
+
+    pub fn palette(&self) -> Palette {
+        self.colorblind_mode.palette()
+    }
+
+    pub fn volume(&self, channel: VolumeChannel) -> u32 {
+        match channel {
+            VolumeChannel::Master => self.master_volume,
+            VolumeChannel::Music => self.music_volume,
+            VolumeChannel::Effects => self.effects_volume,
+        }
+    }
+
+    pub fn volume_mut(&mut self, channel: VolumeChannel) -> &mut u32 {
+        match channel {
+            VolumeChannel::Master => &mut self.master_volume,
+            VolumeChannel::Music => &mut self.music_volume,
+            VolumeChannel::Effects => &mut self.effects_volume,
+        }
+    }
+
+    // Up a step, and back to silent after full volume
+    pub fn next_volume(&self, channel: VolumeChannel) -> u32 {
+        let volume = self.volume(channel);
+        if volume >= 100 {
+            0
+        } else {
+            volume + 10
+        }
+    }
+
+    // How loud a sound on `channel` plays, from 0 to 1, master included
+    pub fn loudness(&self, channel: VolumeChannel) -> f32 {
+        let master = self.master_volume as f32 / 100.0;
+        match channel {
+            VolumeChannel::Master => master,
+            _ => master * self.volume(channel) as f32 / 100.0,
+        }
+    }
+
+    // For spawning a sound on `channel`, spawn the `VolumeChannel` with it so it
+    // follows later changes too
+    pub fn playback(&self, channel: VolumeChannel) -> PlaybackSettings {
+        PlaybackSettings::DESPAWN.with_volume(Volume::new_absolute(self.loudness(channel)))
+    }
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Settings::load())
+            .add_systems(
+                Update,
+                (apply_window_settings, apply_volume, save_settings)
+                    .run_if(resource_changed::<Settings>()),
+            );
+    }
+}
+
+pub fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+    let mode = match settings.window_mode {
+        WindowMode::Windowed => BevyWindowMode::Windowed,
+        WindowMode::Fullscreen => BevyWindowMode::Fullscreen,
+        WindowMode::Borderless => BevyWindowMode::BorderlessFullscreen,
+    };
+    let present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    // Only touch the window when something differs, every write reconfigures it
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+// Sounds read the global volume when they start playing, the ones already playing
+// are set through their sinks
+pub fn apply_volume(
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    sink_query: Query<(&AudioSink, &VolumeChannel)>,
+) {
+    global_volume.volume = VolumeLevel::new(settings.loudness(VolumeChannel::Master));
+    for (sink, channel) in sink_query.iter() {
+        sink.set_volume(settings.loudness(*channel));
+    }
+}
+
+pub fn save_settings(settings: Res<Settings>) {
+    // The first change is the file being loaded, nothing to write back
+    if !settings.is_added() {
+        settings.save();
+    }
+}
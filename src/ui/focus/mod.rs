@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState};
+
+const FOCUSED_BORDER_COLOR: Color = Color::WHITE;
+// How far the stick has to be pushed to count as a menu step
+const STICK_THRESHOLD: f32 = 0.5;
+
+// Buttons that can be reached without a mouse
+#[derive(Component)]
+pub struct Focusable;
+
+#[derive(Component)]
+pub struct Focused;
+
+// Sent when the focused button is confirmed, menus react to it like a click
+#[derive(Event)]
+pub struct ButtonActivated(pub Entity);
+
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ButtonActivated>().add_systems(
+            Update,
+            (
+                navigate_with_gamepad,
+                navigate_with_actions,
+                highlight_focused_button,
+            )
+                .chain(),
+        );
+    }
+}
+
+// Moves focus `step` buttons down (or up when negative), in screen order.
+// Only visible buttons are considered, so overlays take over navigation.
+pub fn move_focus(
+    step: i32,
+    commands: &mut Commands,
+    focusable_query: &Query<(Entity, &GlobalTransform, &InheritedVisibility), With<Focusable>>,
+    focused_query: &Query<Entity, With<Focused>>,
+) {
+    let mut buttons: Vec<(Entity, Vec3)> = focusable_query
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation()))
+        .collect();
+    if buttons.is_empty() {
+        return;
+    }
+    // UI coordinates grow downwards
+    buttons.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let current = focused_query
+        .iter()
+        .find_map(|focused| buttons.iter().position(|(entity, _)| *entity == focused));
+    let next = match current {
+        Some(index) => (index as i32 + step).rem_euclid(buttons.len() as i32) as usize,
+        None => 0,
+    };
+
+    for focused in focused_query.iter() {
+        commands.entity(focused).remove::<Focused>();
+    }
+    commands.entity(buttons[next].0).insert(Focused);
+}
+
+// The D-pad and confirm button are bound actions, only the stick is read here
+pub fn navigate_with_gamepad(
+    mut commands: Commands,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut stick_held: Local<bool>,
+    focusable_query: Query<(Entity, &GlobalTransform, &InheritedVisibility), With<Focusable>>,
+    focused_query: Query<Entity, With<Focused>>,
+) {
+    let mut step = 0;
+    let mut stick_pushed = false;
+    for gamepad in gamepads.iter() {
+        let stick_y = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+            .unwrap_or(0.0);
+        if stick_y.abs() > STICK_THRESHOLD {
+            stick_pushed = true;
+            // Only step once per push
+            if !*stick_held {
+                step = if stick_y > 0.0 { -1 } else { 1 };
+            }
+        }
+    }
+    *stick_held = stick_pushed;
+
+    if step != 0 {
+        move_focus(step, &mut commands, &focusable_query, &focused_query);
+    }
+}
+
+pub fn navigate_with_actions(
+    mut commands: Commands,
+    action_state: Res<ActionState>,
+    keyboard_input: Res<Input<KeyCode>>,
+    focusable_query: Query<(Entity, &GlobalTransform, &InheritedVisibility), With<Focusable>>,
+    focused_query: Query<Entity, With<Focused>>,
+    mut activated_event_writer: EventWriter<ButtonActivated>,
+) {
+    // Shift walks backwards, so Shift+Tab works like it does everywhere else
+    let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let mut step = 0;
+    if action_state.just_pressed(Action::MenuUp) {
+        step = -1;
+    }
+    if action_state.just_pressed(Action::MenuDown) {
+        step = if shift { -1 } else { 1 };
+    }
+
+    if action_state.just_pressed(Action::MenuConfirm) {
+        for focused in focused_query.iter() {
+            activated_event_writer.send(ButtonActivated(focused));
+        }
+    }
+
+    if step != 0 {
+        move_focus(step, &mut commands, &focusable_query, &focused_query);
+    }
+}
+
+pub fn highlight_focused_button(
+    mut button_query: Query<(Has<Focused>, &mut BorderColor), With<Focusable>>,
+) {
+    for (focused, mut border_color) in button_query.iter_mut() {
+        let color = if focused {
+            FOCUSED_BORDER_COLOR
+        } else {
+            Color::NONE
+        };
+        if border_color.0 != color {
+            border_color.0 = color;
+        }
+    }
+}
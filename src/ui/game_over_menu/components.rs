@@ -9,6 +9,9 @@ pub struct FinalScoreText {}
 #[derive(Component)]
 pub struct RestartButton {}
 
+#[derive(Component)]
+pub struct ReplayButton {}
+
 #[derive(Component)]
 pub struct MainMenuButton {}
 
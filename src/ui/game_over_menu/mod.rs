@@ -19,8 +19,10 @@ impl Plugin for GameOverMenuPlugin {
             .add_systems(Update,
                 (
                     interact_with_restart_button,
+                    interact_with_replay_button,
                     interact_with_main_menu_button,
                     interact_with_quit_button,
+                    activate_game_over_menu_button,
                     update_final_score_text,
                 ).run_if(in_state(AppState::GameOver))
             )
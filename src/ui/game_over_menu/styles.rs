@@ -1,7 +1,5 @@
 use bevy::prelude::*;
 
-pub const BACKGROUND_COLOR: Color = Color::rgba(0.25, 0.25, 0.25, 0.5);
-
 pub const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
 pub const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
 pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.35, 0.35);
@@ -36,6 +34,7 @@ pub const BUTTON_STYLE: Style = {
     style.align_items = AlignItems::Center;
     style.width =  Val::Px(200.0);
     style.height =  Val::Px(80.0);
+    style.border = UiRect::all(Val::Px(3.0));
     style
 };
 
@@ -3,19 +3,14 @@ use bevy::prelude::*;
 
 use crate::ui::game_over_menu::components::*;
 use crate::ui::game_over_menu::styles::*;
+use crate::ui::focus::ButtonActivated;
 use crate::AppState;
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct RestartClicked {
     pub value: bool,
 }
 
-impl Default for RestartClicked {
-    fn default() -> RestartClicked {
-        RestartClicked {value: false}
-    }
-}
-
 pub fn insert_restart_clicked(mut commands: Commands) {
     commands.insert_resource(RestartClicked::default())
 }
@@ -24,6 +19,7 @@ pub fn remove_restart_clicked(mut commands: Commands) {
     commands.remove_resource::<RestartClicked>();
 }
 
+#[allow(clippy::type_complexity)]
 pub fn interact_with_restart_button(
     mut restart_clicked: ResMut<RestartClicked>,
     mut button_query: Query<
@@ -41,7 +37,6 @@ pub fn interact_with_restart_button(
             Interaction::Hovered => {
                 if restart_clicked.value {
                     app_state_next_state.set(AppState::Game);
-                    println!("Entered AppState::Game");
                 }
                 *color = HOVERED_BUTTON.into();
             }
@@ -53,6 +48,31 @@ pub fn interact_with_restart_button(
     }
 }
 
+#[allow(clippy::type_complexity)]
+pub fn interact_with_replay_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ReplayButton>),
+    >,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                app_state_next_state.set(AppState::Replay);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
 pub fn interact_with_main_menu_button(
     mut button_query: Query<
         (&Interaction, &mut BackgroundColor),
@@ -76,6 +96,7 @@ pub fn interact_with_main_menu_button(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn interact_with_quit_button(
     mut app_exit_event_writer: EventWriter<AppExit>,
     mut button_query: Query<
@@ -98,3 +119,25 @@ pub fn interact_with_quit_button(
         }
     }
 }
+
+pub fn activate_game_over_menu_button(
+    mut activated_event_reader: EventReader<ButtonActivated>,
+    restart_query: Query<(), With<RestartButton>>,
+    replay_query: Query<(), With<ReplayButton>>,
+    main_menu_query: Query<(), With<MainMenuButton>>,
+    quit_query: Query<(), With<QuitButton>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+) {
+    for ButtonActivated(entity) in activated_event_reader.read() {
+        if restart_query.contains(*entity) {
+            app_state_next_state.set(AppState::Game);
+        } else if replay_query.contains(*entity) {
+            app_state_next_state.set(AppState::Replay);
+        } else if main_menu_query.contains(*entity) {
+            app_state_next_state.set(AppState::MainMenu);
+        } else if quit_query.contains(*entity) {
+            app_exit_event_writer.send(AppExit);
+        }
+    }
+}
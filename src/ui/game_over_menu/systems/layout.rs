@@ -1,20 +1,27 @@
 use bevy::prelude::*;
 
+use crate::locale::Locale;
 use crate::ui::game_over_menu::components::*;
 use crate::ui::game_over_menu::styles::*;
+use crate::ui::focus::Focusable;
 
 pub fn spawn_game_over_menu(
     mut commands: Commands, 
     asset_server: Res<AssetServer>,
     mut window_query: Query<&mut Window>,
     mut backgroung_color: ResMut<ClearColor>,
+    locale: Res<Locale>,
 ) {
     backgroung_color.0 = Color::rgb_u8(43, 44, 47);
     window_query.single_mut().cursor.visible = true;
-    build_game_over_menu(&mut commands, &asset_server);
+    build_game_over_menu(&mut commands, &asset_server, &locale);
 }
 
-pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+pub fn build_game_over_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    locale: &Locale,
+) -> Entity {
     let game_over_menu_entity = commands
         .spawn((
             NodeBundle {
@@ -27,7 +34,6 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
             parent
                 .spawn(NodeBundle {
                     style: GAME_OVER_MENU_CONTAINER_STYLE,
-                    //background_color: BACKGROUND_COLOR.into(),
                     ..default()
                 })
                 .with_children(|parent| {
@@ -35,8 +41,8 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                     parent.spawn(TextBundle {
                         text: Text {
                             sections: vec![TextSection::new(
-                                "Game Over",
-                                get_title_text_style(&asset_server),
+                                locale.get("game_over.title"),
+                                get_title_text_style(asset_server),
                             )],
                             alignment: TextAlignment::Center,
                             ..default()
@@ -48,8 +54,8 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                         TextBundle {
                             text: Text {
                                 sections: vec![TextSection::new(
-                                    "Your final score was:",
-                                    get_final_score_text_style(&asset_server),
+                                    locale.get("game_over.score"),
+                                    get_final_score_text_style(asset_server),
                                 )],
                                 alignment: TextAlignment::Center,
                                 ..default()
@@ -66,14 +72,39 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                                 ..default()
                             },
                             RestartButton {},
+                            Focusable,
                         ))
                         .with_children(|parent| {
                             parent.spawn(TextBundle {
                                 style: Style { ..default() },
                                 text: Text {
                                     sections: vec![TextSection::new(
-                                        "Restart",
-                                        get_button_text_style(&asset_server),
+                                        locale.get("game_over.restart"),
+                                        get_button_text_style(asset_server),
+                                    )],
+                                    alignment: TextAlignment::Center,
+                                    ..default()
+                                },
+                                ..default()
+                            });
+                        });
+                    // Replay Button
+                    parent.spawn((
+                            ButtonBundle {
+                                style: BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            ReplayButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle {
+                                style: Style { ..default() },
+                                text: Text {
+                                    sections: vec![TextSection::new(
+                                        locale.get("menu.replay"),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
@@ -89,14 +120,15 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                                 ..default()
                             },
                             MainMenuButton {},
+                            Focusable,
                         ))
                         .with_children(|parent| {
                             parent.spawn(TextBundle {
                                 style: Style { ..default() },
                                 text: Text {
                                     sections: vec![TextSection::new(
-                                        "Main Menu",
-                                        get_button_text_style(&asset_server),
+                                        locale.get("menu.main_menu"),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
@@ -112,14 +144,15 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                                 ..default()
                             },
                             QuitButton {},
+                            Focusable,
                         ))
                         .with_children(|parent| {
                             parent.spawn(TextBundle {
                                 style: Style { ..default() },
                                 text: Text {
                                     sections: vec![TextSection::new(
-                                        "Quit",
-                                        get_button_text_style(&asset_server),
+                                        locale.get("menu.quit"),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
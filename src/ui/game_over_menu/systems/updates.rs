@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 
-use crate::game::swings_count::Scoreboard;
-use crate::{GameOver, swings_count};
+use crate::game::players::{Player, Players};
+use crate::locale::Locale;
 use crate::ui::game_over_menu::components::FinalScoreText;
+use crate::{FinalScore, GameOver};
 
 //pub fn update_final_score_text(
 //    swings_count: ResMut<Scoreboard>,
@@ -16,10 +17,58 @@ use crate::ui::game_over_menu::components::FinalScoreText;
 pub fn update_final_score_text(
     mut game_over_event_reader: EventReader<GameOver>,
     mut text_query: Query<&mut Text, With<FinalScoreText>>,
+    locale: Res<Locale>,
+    players: Res<Players>,
 ) {
     for event in game_over_event_reader.read() {
+        let value = match event.scores.as_slice() {
+            [final_score] => final_score_text(&locale, final_score),
+            scores => leaderboard_text(&locale, &players, scores),
+        };
         for mut text in text_query.iter_mut() {
-            text.sections[0].value = format!("Final Score: {}", event.final_score.to_string());
+            text.sections[0].value = value.clone();
         }
     }
 }
+
+fn final_score_text(locale: &Locale, final_score: &FinalScore) -> String {
+    if final_score.mulligans > 0 {
+        locale.format(
+            "game_over.final_score_mulligans",
+            &[
+                &final_score.score.to_string(),
+                &final_score.mulligans.to_string(),
+            ],
+        )
+    } else {
+        locale.format("game_over.final_score", &[&final_score.score.to_string()])
+    }
+}
+
+// Fewest strokes first, players on the same score share the place
+fn leaderboard_text(locale: &Locale, players: &Players, scores: &[FinalScore]) -> String {
+    let mut ranking = scores.to_vec();
+    ranking.sort_by_key(|final_score| (final_score.score, final_score.player));
+
+    ranking
+        .iter()
+        .map(|final_score| {
+            let place = 1 + ranking
+                .iter()
+                .filter(|other| other.score < final_score.score)
+                .count();
+            let place = place.to_string();
+            let name = players.name(Player(final_score.player), locale);
+            let score = final_score.score.to_string();
+            if final_score.mulligans > 0 {
+                locale.format(
+                    "game_over.leaderboard_row_mulligans",
+                    &[&place, &name, &score, &final_score.mulligans.to_string()],
+                )
+            } else {
+                locale.format("game_over.leaderboard_row", &[&place, &name, &score])
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
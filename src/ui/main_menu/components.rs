@@ -6,5 +6,26 @@ pub struct MainMenu{}
 #[derive(Component)]
 pub struct PlayButton{}
 
+#[derive(Component)]
+pub struct EndlessButton{}
+
+#[derive(Component)]
+pub struct PlayersButton{}
+
+#[derive(Component)]
+pub struct PlayersText{}
+
+#[derive(Component)]
+pub struct ComputerButton{}
+
+#[derive(Component)]
+pub struct ComputerText{}
+
+#[derive(Component)]
+pub struct ReplayButton{}
+
+#[derive(Component)]
+pub struct SettingsButton{}
+
 #[derive(Component)]
 pub struct QuitButton{}
\ No newline at end of file
@@ -5,7 +5,11 @@ mod systems;
 use bevy::prelude::*;
 use systems::layout::*;
 use systems::interactions::*;
+use systems::updates::*;
 use crate::AppState;
+use crate::game::players::Players;
+use crate::locale::Locale;
+use crate::ui::settings_menu::SettingsMenuState;
 
 pub struct MainMenuPlugin;
 
@@ -15,8 +19,18 @@ impl Plugin for MainMenuPlugin {
         .add_systems(OnEnter(AppState::MainMenu),spawn_main_menu)
         .add_systems(Update,(
             interact_with_play_button,
-            interact_with_quit_button
+            interact_with_endless_button,
+            interact_with_players_button,
+            interact_with_computer_button,
+            interact_with_replay_button,
+            interact_with_settings_button,
+            interact_with_quit_button,
+            activate_main_menu_button,
+            update_players_text
+                .run_if(resource_changed::<Players>().or_else(resource_changed::<Locale>())),
         ).run_if(in_state(AppState::MainMenu)))
+        .add_systems(OnExit(SettingsMenuState::Closed),hide_main_menu)
+        .add_systems(OnEnter(SettingsMenuState::Closed),show_main_menu)
         .add_systems(OnExit(AppState::MainMenu),despawn_main_menu)
         .add_systems(OnExit(AppState::MainMenu),remove_play_clicked);
     }
@@ -10,6 +10,7 @@ pub const BUTTON_STYLE: Style = {
     style.align_items = AlignItems::Center;
     style.width =  Val::Px(200.0);
     style.height =  Val::Px(80.0);
+    style.border = UiRect::all(Val::Px(3.0));
     style
 };
 
@@ -59,10 +60,3 @@ pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     }
 }
 
-pub fn get_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
-    TextStyle {
-        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-        font_size: 128.0,
-        color: Color::WHITE,
-    }
-}
\ No newline at end of file
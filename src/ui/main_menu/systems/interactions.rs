@@ -1,21 +1,19 @@
 use bevy::app::AppExit;
 use bevy::prelude::*;
 
+use crate::game::levels::Course;
+use crate::game::players::Players;
 use crate::ui::main_menu::components::*;
 use crate::ui::main_menu::styles::*;
+use crate::ui::focus::ButtonActivated;
+use crate::ui::settings_menu::SettingsMenuState;
 use crate::AppState;
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct PlayClicked {
     pub value: bool,
 }
 
-impl Default for PlayClicked {
-    fn default() -> PlayClicked {
-        PlayClicked {value: false}
-    }
-}
-
 pub fn insert_play_clicked(mut commands: Commands) {
     commands.insert_resource(PlayClicked::default())
 }
@@ -24,10 +22,12 @@ pub fn remove_play_clicked(mut commands: Commands) {
     commands.remove_resource::<PlayClicked>();
 }
 
+#[allow(clippy::type_complexity)]
 pub fn interact_with_play_button (
     mut play_clicked: ResMut<PlayClicked>,
     mut button_query: Query<(&Interaction, &mut BackgroundColor),(Changed<Interaction>, With<PlayButton>)>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut course: ResMut<Course>,
 ) {
     if let Ok((interaction, mut backgroud_color)) = button_query.get_single_mut() {
         match *interaction {
@@ -37,8 +37,8 @@ pub fn interact_with_play_button (
             }
             Interaction::Hovered => {
                 if play_clicked.value {
+                    *course = Course::Classic;
                     app_state_next_state.set(AppState::Game);
-                    println!("Entered AppState::Game");
                 }
                 *backgroud_color = HOVERED_BUTTON_COLOR.into();
             }
@@ -50,6 +50,118 @@ pub fn interact_with_play_button (
     }
 }
 
+// Generated holes one after another until the player has had enough
+#[allow(clippy::type_complexity)]
+pub fn interact_with_endless_button (
+    mut button_query: Query<(&Interaction, &mut BackgroundColor),(Changed<Interaction>, With<EndlessButton>)>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut course: ResMut<Course>,
+    time: Res<Time<Real>>,
+) {
+    if let Ok((interaction, mut backgroud_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *backgroud_color = PRESSED_BUTTON_COLOR.into();
+                *course = Course::new_endless(&time);
+                app_state_next_state.set(AppState::Game);
+            }
+            Interaction::Hovered => {
+                *backgroud_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *backgroud_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+// Steps through one to four players, back to one after four
+#[allow(clippy::type_complexity)]
+pub fn interact_with_players_button (
+    mut button_query: Query<(&Interaction, &mut BackgroundColor),(Changed<Interaction>, With<PlayersButton>)>,
+    mut players: ResMut<Players>,
+) {
+    if let Ok((interaction, mut backgroud_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *backgroud_color = PRESSED_BUTTON_COLOR.into();
+                players.count = players.next();
+            }
+            Interaction::Hovered => {
+                *backgroud_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *backgroud_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+// Off, then each difficulty, the computer takes the last seat
+#[allow(clippy::type_complexity)]
+pub fn interact_with_computer_button (
+    mut button_query: Query<(&Interaction, &mut BackgroundColor),(Changed<Interaction>, With<ComputerButton>)>,
+    mut players: ResMut<Players>,
+) {
+    if let Ok((interaction, mut backgroud_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *backgroud_color = PRESSED_BUTTON_COLOR.into();
+                players.computer = players.next_computer();
+            }
+            Interaction::Hovered => {
+                *backgroud_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *backgroud_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_replay_button (
+    mut button_query: Query<(&Interaction, &mut BackgroundColor),(Changed<Interaction>, With<ReplayButton>)>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    if let Ok((interaction, mut backgroud_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *backgroud_color = PRESSED_BUTTON_COLOR.into();
+                app_state_next_state.set(AppState::Replay);
+            }
+            Interaction::Hovered => {
+                *backgroud_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *backgroud_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_settings_button (
+    mut button_query: Query<(&Interaction, &mut BackgroundColor),(Changed<Interaction>, With<SettingsButton>)>,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+) {
+    if let Ok((interaction, mut backgroud_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *backgroud_color = PRESSED_BUTTON_COLOR.into();
+                settings_menu_next_state.set(SettingsMenuState::Open);
+            }
+            Interaction::Hovered => {
+                *backgroud_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *backgroud_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
 pub fn interact_with_quit_button (
     mut button_query: Query<(&Interaction, &mut BackgroundColor),(Changed<Interaction>, With<QuitButton>)>,
     mut app_exit_event_writer: EventWriter<AppExit>,
@@ -70,3 +182,40 @@ pub fn interact_with_quit_button (
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn activate_main_menu_button (
+    mut activated_event_reader: EventReader<ButtonActivated>,
+    play_query: Query<(), With<PlayButton>>,
+    endless_query: Query<(), With<EndlessButton>>,
+    players_query: Query<(), With<PlayersButton>>,
+    computer_query: Query<(), With<ComputerButton>>,
+    replay_query: Query<(), With<ReplayButton>>,
+    settings_query: Query<(), With<SettingsButton>>,
+    quit_query: Query<(), With<QuitButton>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+    mut players: ResMut<Players>,
+    mut course: ResMut<Course>,
+    time: Res<Time<Real>>,
+) {
+    for ButtonActivated(entity) in activated_event_reader.read() {
+        if play_query.contains(*entity) {
+            *course = Course::Classic;
+            app_state_next_state.set(AppState::Game);
+        } else if endless_query.contains(*entity) {
+            *course = Course::new_endless(&time);
+            app_state_next_state.set(AppState::Game);
+        } else if players_query.contains(*entity) {
+            players.count = players.next();
+        } else if computer_query.contains(*entity) {
+            players.computer = players.next_computer();
+        } else if replay_query.contains(*entity) {
+            app_state_next_state.set(AppState::Replay);
+        } else if settings_query.contains(*entity) {
+            settings_menu_next_state.set(SettingsMenuState::Open);
+        } else if quit_query.contains(*entity) {
+            app_exit_event_writer.send(AppExit);
+        }
+    }
+}
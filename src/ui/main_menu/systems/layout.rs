@@ -1,13 +1,20 @@
 use bevy::prelude::*;
 
+use crate::game::players::Players;
+use crate::locale::{Locale, Localized};
+use crate::net::Disconnected;
 use crate::ui::main_menu::components::*;
 use crate::ui::main_menu::styles::*;
+use crate::ui::focus::Focusable;
 
 pub fn spawn_main_menu(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    locale: Res<Locale>,
+    players: Res<Players>,
+    disconnected: Option<Res<Disconnected>>,
 ) {
-    let main_menu_entity = build_main_menu(&mut commands, &asset_server);
+    build_main_menu(&mut commands, &asset_server, &locale, &players, disconnected.is_some());
 }
 
 pub fn despawn_main_menu(
@@ -21,7 +28,10 @@ pub fn despawn_main_menu(
 
 pub fn build_main_menu(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>
+    asset_server: &Res<AssetServer>,
+    locale: &Locale,
+    players: &Players,
+    disconnected: bool,
 ) -> Entity {
     let main_menu_entity = commands.spawn(
         (NodeBundle {
@@ -46,20 +56,6 @@ pub fn build_main_menu(
                     ..default()
                 }
             );
-            // Text
-//            parent.spawn(
-//                TextBundle {
-//                    text: Text {
-//                        sections: vec![
-//                            TextSection::new("Golfy", get_title_text_style(&asset_server),)
-//                        ],
-//                        alignment: TextAlignment::Center,
-//                        ..default()
-//                    },
-//                    ..default()
-//                }
-//            );
-
             parent.spawn(
                 ImageBundle {
                     style: TITLE_IMAGE_STYLE,
@@ -78,6 +74,22 @@ pub fn build_main_menu(
             );
         });
 
+        // Why the online round ended
+        if disconnected {
+            parent.spawn(
+                (TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(locale.get("menu.disconnected"), get_button_text_style(asset_server),)
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }, Localized { key: "menu.disconnected" })
+            );
+        }
+
         // Play Button
         parent.spawn(
             (
@@ -86,20 +98,146 @@ pub fn build_main_menu(
                     background_color: NORMAL_BUTTON_COLOR.into(),
                     ..default()
                 },
-                PlayButton {}
+                PlayButton {},
+                Focusable,
             )
         ).with_children(|parent| {
             parent.spawn(
-                TextBundle {
+                (TextBundle {
                     text: Text {
                         sections: vec![
-                            TextSection::new("Play", get_button_text_style(&asset_server),)
+                            TextSection::new(locale.get("menu.play"), get_button_text_style(asset_server),)
                         ],
                         alignment: TextAlignment::Center,
                         ..default()
                     },
                     ..default()
-                }
+                }, Localized { key: "menu.play" })
+            );
+        });
+        // Endless Button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                EndlessButton {},
+                Focusable,
+            )
+        ).with_children(|parent| {
+            parent.spawn(
+                (TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(locale.get("menu.endless"), get_button_text_style(asset_server),)
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }, Localized { key: "menu.endless" })
+            );
+        });
+        // Players Button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                PlayersButton {},
+                Focusable,
+            )
+        ).with_children(|parent| {
+            parent.spawn(
+                (TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(players_label(players, locale), get_button_text_style(asset_server),)
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }, PlayersText {})
+            );
+        });
+        // Computer Button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                ComputerButton {},
+                Focusable,
+            )
+        ).with_children(|parent| {
+            parent.spawn(
+                (TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(computer_label(players, locale), get_button_text_style(asset_server),)
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }, ComputerText {})
+            );
+        });
+        // Replay Button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                ReplayButton {},
+                Focusable,
+            )
+        ).with_children(|parent| {
+            parent.spawn(
+                (TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(locale.get("menu.replay"), get_button_text_style(asset_server),)
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }, Localized { key: "menu.replay" })
+            );
+        });
+        // Settings Button
+        parent.spawn(
+            (
+                ButtonBundle {
+                    style: BUTTON_STYLE,
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                SettingsButton {},
+                Focusable,
+            )
+        ).with_children(|parent| {
+            parent.spawn(
+                (TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection::new(locale.get("menu.settings"), get_button_text_style(asset_server),)
+                        ],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                }, Localized { key: "menu.settings" })
             );
         });
         // Quit Button
@@ -110,24 +248,49 @@ pub fn build_main_menu(
                     background_color: NORMAL_BUTTON_COLOR.into(),
                     ..default()
                 },
-                QuitButton {}
+                QuitButton {},
+                Focusable,
             )
         ).with_children(|parent| {
             parent.spawn(
-                TextBundle {
+                (TextBundle {
                     text: Text {
                         sections: vec![
-                            TextSection::new("Quit", get_button_text_style(&asset_server),)
+                            TextSection::new(locale.get("menu.quit"), get_button_text_style(asset_server),)
                         ],
                         alignment: TextAlignment::Center,
                         ..default()
                     },
                     ..default()
-                }
+                }, Localized { key: "menu.quit" })
             );
         });
     })
     .id();
 
     main_menu_entity
-}
\ No newline at end of file
+}
+
+pub fn players_label(players: &Players, locale: &Locale) -> String {
+    locale.format("menu.players", &[&players.count.to_string()])
+}
+
+pub fn computer_label(players: &Players, locale: &Locale) -> String {
+    let difficulty = players
+        .computer
+        .map_or("settings.off", |difficulty| difficulty.key());
+    locale.format("menu.computer", &[locale.get(difficulty)])
+}
+
+// The settings overlay takes over, so hide the menu and its buttons underneath it
+pub fn hide_main_menu(mut main_menu_query: Query<&mut Visibility, With<MainMenu>>) {
+    for mut visibility in main_menu_query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+pub fn show_main_menu(mut main_menu_query: Query<&mut Visibility, With<MainMenu>>) {
+    for mut visibility in main_menu_query.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+}
@@ -1,2 +1,3 @@
 pub mod interactions;
-pub mod layout;
\ No newline at end of file
+pub mod layout;
+pub mod updates;
\ No newline at end of file
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::game::players::Players;
+use crate::locale::Locale;
+use crate::ui::main_menu::components::{ComputerText, PlayersText};
+use crate::ui::main_menu::systems::layout::{computer_label, players_label};
+
+#[allow(clippy::type_complexity)]
+pub fn update_players_text(
+    players: Res<Players>,
+    locale: Res<Locale>,
+    mut players_text_query: Query<&mut Text, (With<PlayersText>, Without<ComputerText>)>,
+    mut computer_text_query: Query<&mut Text, With<ComputerText>>,
+) {
+    for mut text in players_text_query.iter_mut() {
+        text.sections[0].value = players_label(&players, &locale);
+    }
+    for mut text in computer_text_query.iter_mut() {
+        text.sections[0].value = computer_label(&players, &locale);
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::{Component, Entity};
+
+#[derive(Component)]
+pub struct Minimap {}
+
+// Marker of a player's ball, spawned once the ball is on the course
+#[derive(Component)]
+pub struct MinimapBall {
+    pub ball: Entity,
+}
+
+// Outline of what the main camera currently shows
+#[derive(Component)]
+pub struct MinimapView {}
@@ -0,0 +1,45 @@
+pub mod components;
+mod styles;
+mod systems;
+
+use systems::interactions::*;
+use systems::layout::*;
+use systems::updates::*;
+
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+use crate::actions::update_action_state;
+use crate::game::levels::compute_level_bounds;
+use crate::game::{GameState, PauseState};
+use crate::AppState;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // OnEnter State Systems
+            .add_systems(
+                OnEnter(GameState::Flyover),
+                spawn_minimap
+                    .after(compute_level_bounds)
+                    .run_if(in_state(AppState::Game)),
+            )
+            .add_systems(
+                PreUpdate,
+                pan_with_minimap
+                    .after(UiSystem::Focus)
+                    .after(update_action_state)
+                    .run_if(in_state(GameState::DeadBall))
+                    .run_if(in_state(PauseState::Running)),
+            )
+            .add_systems(
+                Update,
+                (update_minimap, update_minimap_balls).run_if(in_state(AppState::Game)),
+            )
+            // OnExit State Systems
+            .add_systems(OnEnter(GameState::LoadingMap), despawn_minimap)
+            .add_systems(OnExit(AppState::Game), despawn_minimap);
+    }
+}
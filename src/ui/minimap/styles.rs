@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+// Largest the map can get, the hole's aspect ratio decides the rest
+pub const MINIMAP_MAX_SIZE: Vec2 = Vec2::new(320.0, 200.0);
+pub const MINIMAP_MARGIN: Val = Val::Px(16.0);
+pub const MARKER_SIZE: f32 = 8.0;
+// The ball whose turn it is stands out from the others
+pub const ACTIVE_MARKER_SIZE: f32 = 12.0;
+pub const ACTIVE_MARKER_BORDER: Val = Val::Px(2.0);
+pub const ACTIVE_MARKER_BORDER_COLOR: Color = Color::BLACK;
+
+pub const BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.2, 0.1, 0.75);
+pub const VIEW_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.6);
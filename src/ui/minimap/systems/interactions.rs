@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::actions::{Action, ActionState, Pointer};
+use crate::camera::follow::{CameraMode, CameraRig};
+use crate::game::levels::LevelBounds;
+use crate::ui::minimap::components::Minimap;
+use crate::ui::minimap::systems::layout::minimap_scale;
+
+// Pressing or dragging on the map moves the camera there. Runs before `Update`
+// so the press never reaches the aiming systems.
+pub fn pan_with_minimap(
+    minimap_query: Query<(&Interaction, &Node, &GlobalTransform), With<Minimap>>,
+    pointer: Res<Pointer>,
+    ui_scale: Res<UiScale>,
+    level_bounds: Res<LevelBounds>,
+    mut action_state: ResMut<ActionState>,
+    mut rig: ResMut<CameraRig>,
+) {
+    let Ok((interaction, node, transform)) = minimap_query.get_single() else {
+        return;
+    };
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    action_state.consume(Action::Shoot);
+
+    let Some(position) = pointer.position else {
+        return;
+    };
+    let top_left = transform.translation().truncate() - node.size() / 2.0;
+    // UI nodes are laid out in scaled units, the pointer is in window units
+    let local = position / ui_scale.0 as f32 - top_left;
+    let bounds = level_bounds.0;
+    let scale = minimap_scale(bounds);
+
+    rig.mode = CameraMode::Survey;
+    rig.focus = Vec2::new(
+        bounds.min.x + local.x / scale,
+        bounds.max.y - local.y / scale,
+    );
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::camera::follow::overview_zoom;
+use crate::game::components::{Collider, GolfHole, Water};
+use crate::game::levels::LevelBounds;
+use crate::game::systems::pond_from_transform;
+use crate::settings::Settings;
+use crate::ui::minimap::components::*;
+use crate::ui::minimap::styles::*;
+
+// Map pixels per world unit
+pub fn minimap_scale(bounds: Rect) -> f32 {
+    let size = bounds.size().max(Vec2::ONE);
+    (MINIMAP_MAX_SIZE.x / size.x).min(MINIMAP_MAX_SIZE.y / size.y)
+}
+
+// Places a world rectangle on the map, UI coordinates grow downwards
+pub fn minimap_rect_style(rect: Rect, bounds: Rect, scale: f32) -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        left: Val::Px((rect.min.x - bounds.min.x) * scale),
+        top: Val::Px((bounds.max.y - rect.max.y) * scale),
+        width: Val::Px(rect.width() * scale),
+        height: Val::Px(rect.height() * scale),
+        ..default()
+    }
+}
+
+pub fn minimap_marker_style(position: Vec2, size: f32, bounds: Rect, scale: f32) -> Style {
+    let marker = Rect::from_center_size(position, Vec2::splat(size / scale));
+    minimap_rect_style(marker, bounds, scale)
+}
+
+pub fn spawn_minimap(
+    mut commands: Commands,
+    level_bounds: Res<LevelBounds>,
+    wall_query: Query<&Transform, With<Collider>>,
+    water_query: Query<&Transform, With<Water>>,
+    hole_query: Query<&Transform, With<GolfHole>>,
+    settings: Res<Settings>,
+) {
+    let bounds = level_bounds.0;
+    // Holes that fit on screen don't need a map
+    if overview_zoom(bounds) <= 1.0 {
+        return;
+    }
+    let scale = minimap_scale(bounds);
+    let palette = settings.palette();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: MINIMAP_MARGIN,
+                    right: MINIMAP_MARGIN,
+                    width: Val::Px(bounds.width() * scale),
+                    height: Val::Px(bounds.height() * scale),
+                    ..default()
+                },
+                background_color: BACKGROUND_COLOR.into(),
+                // Clicks on the map pan the camera instead of reaching the course
+                focus_policy: FocusPolicy::Block,
+                ..default()
+            },
+            Interaction::default(),
+            Minimap {},
+        ))
+        .with_children(|parent| {
+            // Walls
+            for transform in wall_query.iter() {
+                let wall = Rect::from_center_size(
+                    transform.translation.truncate(),
+                    transform.scale.truncate(),
+                );
+                parent.spawn(NodeBundle {
+                    style: minimap_rect_style(wall, bounds, scale),
+                    background_color: palette.wall.into(),
+                    ..default()
+                });
+            }
+            // Ponds, over the walls like on the course
+            for transform in water_query.iter() {
+                parent.spawn(NodeBundle {
+                    style: minimap_rect_style(pond_from_transform(transform), bounds, scale),
+                    background_color: palette.water.into(),
+                    ..default()
+                });
+            }
+            // Cup
+            for transform in hole_query.iter() {
+                parent.spawn(NodeBundle {
+                    style: minimap_marker_style(
+                        transform.translation.truncate(),
+                        MARKER_SIZE,
+                        bounds,
+                        scale,
+                    ),
+                    background_color: palette.cup.into(),
+                    ..default()
+                });
+            }
+            // Balls are added by `update_minimap_balls`, the other players' ones
+            // only reach the course after this runs
+            // Camera view
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..minimap_rect_style(bounds, bounds, scale)
+                    },
+                    border_color: VIEW_COLOR.into(),
+                    ..default()
+                },
+                MinimapView {},
+            ));
+        });
+}
+
+pub fn despawn_minimap(mut commands: Commands, minimap_query: Query<Entity, With<Minimap>>) {
+    for minimap_entity in minimap_query.iter() {
+        commands.entity(minimap_entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+
+use crate::camera::MainCamera;
+use crate::game::components::Ball;
+use crate::game::levels::LevelBounds;
+use crate::game::players::{ActiveBall, Holed, Player};
+use crate::settings::Settings;
+use crate::ui::minimap::components::*;
+use crate::ui::minimap::styles::*;
+use crate::ui::minimap::systems::layout::{
+    minimap_marker_style, minimap_rect_style, minimap_scale,
+};
+use crate::RESOLUTION;
+
+pub fn update_minimap(
+    settings: Res<Settings>,
+    level_bounds: Res<LevelBounds>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    mut minimap_query: Query<&mut Visibility, With<Minimap>>,
+    mut view_query: Query<&mut Style, With<MinimapView>>,
+) {
+    for mut visibility in minimap_query.iter_mut() {
+        *visibility = if settings.show_minimap {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    let bounds = level_bounds.0;
+    let scale = minimap_scale(bounds);
+
+    if let Ok((camera_transform, projection)) = camera_query.get_single() {
+        let view = Rect::from_center_size(
+            camera_transform.translation.truncate(),
+            RESOLUTION * projection.scale,
+        );
+        let frame = minimap_rect_style(view.intersect(bounds), bounds, scale);
+        for mut style in view_query.iter_mut() {
+            style.left = frame.left;
+            style.top = frame.top;
+            style.width = frame.width;
+            style.height = frame.height;
+        }
+    }
+}
+
+// One marker per player's ball in that player's color, the active one bigger and
+// outlined. Holed balls are hidden, gone ones lose their marker.
+#[allow(clippy::type_complexity)]
+pub fn update_minimap_balls(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    level_bounds: Res<LevelBounds>,
+    minimap_query: Query<Entity, With<Minimap>>,
+    ball_query: Query<(Entity, &Transform, &Player, Has<ActiveBall>, Has<Holed>), With<Ball>>,
+    mut marker_query: Query<(
+        Entity,
+        &MinimapBall,
+        &mut Style,
+        &mut BackgroundColor,
+        &mut BorderColor,
+        &mut Visibility,
+    )>,
+) {
+    let Ok(minimap) = minimap_query.get_single() else {
+        return;
+    };
+    let bounds = level_bounds.0;
+    let scale = minimap_scale(bounds);
+    let palette = settings.palette();
+
+    for (marker_entity, marker, ..) in marker_query.iter() {
+        if !ball_query.contains(marker.ball) {
+            commands.entity(marker_entity).despawn_recursive();
+        }
+    }
+
+    for (ball, transform, player, active, holed) in ball_query.iter() {
+        let (size, border, border_color) = if active {
+            (
+                ACTIVE_MARKER_SIZE,
+                ACTIVE_MARKER_BORDER,
+                ACTIVE_MARKER_BORDER_COLOR,
+            )
+        } else {
+            (MARKER_SIZE, Val::ZERO, Color::NONE)
+        };
+        let marker_style = Style {
+            border: UiRect::all(border),
+            ..minimap_marker_style(transform.translation.truncate(), size, bounds, scale)
+        };
+        let visibility = if holed {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+
+        match marker_query
+            .iter_mut()
+            .find(|(_, marker, ..)| marker.ball == ball)
+        {
+            Some((
+                _,
+                _,
+                mut style,
+                mut background_color,
+                mut marker_border_color,
+                mut marker_visibility,
+            )) => {
+                *style = marker_style;
+                *background_color = palette.ball_of(player.0).into();
+                *marker_border_color = border_color.into();
+                *marker_visibility = visibility;
+            }
+            None => {
+                commands.entity(minimap).with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: marker_style,
+                            background_color: palette.ball_of(player.0).into(),
+                            border_color: border_color.into(),
+                            visibility,
+                            ..default()
+                        },
+                        MinimapBall { ball },
+                    ));
+                });
+            }
+        }
+    }
+}
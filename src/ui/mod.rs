@@ -1,8 +1,16 @@
+pub mod focus;
 mod game_over_menu;
 mod main_menu;
+pub mod minimap;
+mod pause_menu;
+pub mod settings_menu;
 
+use focus::FocusPlugin;
 use game_over_menu::GameOverMenuPlugin;
 use main_menu::MainMenuPlugin;
+use minimap::MinimapPlugin;
+use pause_menu::PauseMenuPlugin;
+use settings_menu::SettingsMenuPlugin;
 
 use bevy::prelude::*;
 
@@ -12,7 +20,11 @@ impl Plugin for GameUIPlugin {
     fn build(&self, app: &mut App) {
         app
             // Plugins
+            .add_plugins(FocusPlugin)
             .add_plugins(MainMenuPlugin)
-            .add_plugins(GameOverMenuPlugin);
+            .add_plugins(GameOverMenuPlugin)
+            .add_plugins(PauseMenuPlugin)
+            .add_plugins(MinimapPlugin)
+            .add_plugins(SettingsMenuPlugin);
     }
 }
@@ -0,0 +1,16 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct PauseMenu {}
+
+#[derive(Component)]
+pub struct ResumeButton {}
+
+#[derive(Component)]
+pub struct SettingsButton {}
+
+#[derive(Component)]
+pub struct MainMenuButton {}
+
+#[derive(Component)]
+pub struct QuitButton {}
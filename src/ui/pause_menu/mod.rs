@@ -0,0 +1,39 @@
+mod components;
+mod styles;
+mod systems;
+
+use systems::interactions::*;
+use systems::layout::*;
+
+use bevy::prelude::*;
+
+use crate::game::PauseState;
+use crate::ui::settings_menu::SettingsMenuState;
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // OnEnter State Systems
+            .add_systems(OnEnter(PauseState::Paused), spawn_pause_menu)
+            .add_systems(
+                Update,
+                (
+                    interact_with_resume_button,
+                    interact_with_settings_button,
+                    interact_with_main_menu_button,
+                    interact_with_quit_button,
+                    activate_pause_menu_button,
+                )
+                    .run_if(in_state(PauseState::Paused)),
+            )
+            .add_systems(OnExit(SettingsMenuState::Closed), hide_pause_menu)
+            .add_systems(OnEnter(SettingsMenuState::Closed), show_pause_menu)
+            // OnExit State Systems
+            .add_systems(
+                OnExit(PauseState::Paused),
+                (despawn_pause_menu, close_settings_menu),
+            );
+    }
+}
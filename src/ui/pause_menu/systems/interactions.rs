@@ -0,0 +1,134 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::game::{GameState, PauseState};
+use crate::ui::focus::ButtonActivated;
+use crate::ui::pause_menu::components::*;
+use crate::ui::pause_menu::styles::*;
+use crate::ui::settings_menu::SettingsMenuState;
+use crate::AppState;
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_resume_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ResumeButton>),
+    >,
+    mut pause_state_next_state: ResMut<NextState<PauseState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                pause_state_next_state.set(PauseState::Running);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_settings_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<SettingsButton>),
+    >,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings_menu_next_state.set(SettingsMenuState::Open);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_main_menu_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<MainMenuButton>),
+    >,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                game_state_next_state.set(GameState::OutOfGame);
+                app_state_next_state.set(AppState::MainMenu);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_quit_button(
+    mut app_exit_event_writer: EventWriter<AppExit>,
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<QuitButton>),
+    >,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                app_exit_event_writer.send(AppExit);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn activate_pause_menu_button(
+    mut activated_event_reader: EventReader<ButtonActivated>,
+    resume_query: Query<(), With<ResumeButton>>,
+    settings_query: Query<(), With<SettingsButton>>,
+    main_menu_query: Query<(), With<MainMenuButton>>,
+    quit_query: Query<(), With<QuitButton>>,
+    mut pause_state_next_state: ResMut<NextState<PauseState>>,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+) {
+    for ButtonActivated(entity) in activated_event_reader.read() {
+        if resume_query.contains(*entity) {
+            pause_state_next_state.set(PauseState::Running);
+        } else if settings_query.contains(*entity) {
+            settings_menu_next_state.set(SettingsMenuState::Open);
+        } else if main_menu_query.contains(*entity) {
+            game_state_next_state.set(GameState::OutOfGame);
+            app_state_next_state.set(AppState::MainMenu);
+        } else if quit_query.contains(*entity) {
+            app_exit_event_writer.send(AppExit);
+        }
+    }
+}
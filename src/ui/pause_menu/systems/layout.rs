@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::locale::{Locale, Localized};
+use crate::ui::focus::Focusable;
+use crate::ui::pause_menu::components::*;
+use crate::ui::pause_menu::styles::*;
+use crate::ui::settings_menu::SettingsMenuState;
+
+pub fn spawn_pause_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    locale: Res<Locale>,
+) {
+    build_pause_menu(&mut commands, &asset_server, &locale);
+}
+
+pub fn despawn_pause_menu(
+    mut commands: Commands,
+    pause_menu_query: Query<Entity, With<PauseMenu>>,
+) {
+    if let Ok(pause_menu_entity) = pause_menu_query.get_single() {
+        commands.entity(pause_menu_entity).despawn_recursive();
+    }
+}
+
+fn spawn_button(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    locale: &Locale,
+    key: &'static str,
+    marker: impl Bundle,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: BUTTON_STYLE,
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            marker,
+            Focusable,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text {
+                        sections: vec![TextSection::new(
+                            locale.get(key),
+                            get_button_text_style(asset_server),
+                        )],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Localized { key },
+            ));
+        });
+}
+
+pub fn build_pause_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    locale: &Locale,
+) -> Entity {
+    let pause_menu_entity = commands
+        .spawn((
+            NodeBundle {
+                style: PAUSE_MENU_STYLE,
+                background_color: BACKGROUND_COLOR.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(5),
+                ..default()
+            },
+            PauseMenu {},
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                TextBundle {
+                    text: Text {
+                        sections: vec![TextSection::new(
+                            locale.get("pause.title"),
+                            get_title_text_style(asset_server),
+                        )],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Localized { key: "pause.title" },
+            ));
+            spawn_button(parent, asset_server, locale, "pause.resume", ResumeButton {});
+            spawn_button(parent, asset_server, locale, "menu.settings", SettingsButton {});
+            spawn_button(parent, asset_server, locale, "menu.main_menu", MainMenuButton {});
+            spawn_button(parent, asset_server, locale, "menu.quit", QuitButton {});
+        })
+        .id();
+
+    pause_menu_entity
+}
+
+pub fn hide_pause_menu(mut pause_menu_query: Query<&mut Visibility, With<PauseMenu>>) {
+    for mut visibility in pause_menu_query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+pub fn show_pause_menu(mut pause_menu_query: Query<&mut Visibility, With<PauseMenu>>) {
+    for mut visibility in pause_menu_query.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+// Unpausing while the settings are open would leave them over the game
+pub fn close_settings_menu(mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>) {
+    settings_menu_next_state.set(SettingsMenuState::Closed);
+}
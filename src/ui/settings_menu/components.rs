@@ -0,0 +1,96 @@
+use bevy::prelude::Component;
+
+use crate::actions::Action;
+use crate::settings::VolumeChannel;
+
+#[derive(Component)]
+pub struct SettingsMenu {}
+
+#[derive(Component)]
+pub struct AimingModeButton {}
+
+#[derive(Component)]
+pub struct AimingModeText {}
+
+#[derive(Component)]
+pub struct AccuracyClickButton {}
+
+#[derive(Component)]
+pub struct AccuracyClickText {}
+
+#[derive(Component)]
+pub struct DifficultyButton {}
+
+#[derive(Component)]
+pub struct DifficultyText {}
+
+#[derive(Component)]
+pub struct MinimapButton {}
+
+#[derive(Component)]
+pub struct MinimapText {}
+
+#[derive(Component)]
+pub struct GhostButton {}
+
+#[derive(Component)]
+pub struct GhostText {}
+
+#[derive(Component)]
+pub struct WindowModeButton {}
+
+#[derive(Component)]
+pub struct WindowModeText {}
+
+#[derive(Component)]
+pub struct VsyncButton {}
+
+#[derive(Component)]
+pub struct VsyncText {}
+
+#[derive(Component)]
+pub struct VolumeButton {
+    pub channel: VolumeChannel,
+}
+
+#[derive(Component)]
+pub struct VolumeText {
+    pub channel: VolumeChannel,
+}
+
+#[derive(Component)]
+pub struct ColorblindButton {}
+
+#[derive(Component)]
+pub struct ColorblindText {}
+
+#[derive(Component)]
+pub struct LanguageButton {}
+
+#[derive(Component)]
+pub struct LanguageText {}
+
+#[derive(Component)]
+pub struct BackButton {}
+
+#[derive(Component)]
+pub struct ControlsButton {}
+
+#[derive(Component)]
+pub struct ControlsMenu {}
+
+#[derive(Component)]
+pub struct ActionBindingButton {
+    pub action: Action,
+}
+
+#[derive(Component)]
+pub struct ActionBindingText {
+    pub action: Action,
+}
+
+#[derive(Component)]
+pub struct ResetBindingsButton {}
+
+#[derive(Component)]
+pub struct ControlsBackButton {}
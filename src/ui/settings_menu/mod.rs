@@ -0,0 +1,86 @@
+mod components;
+mod styles;
+mod systems;
+
+use systems::interactions::*;
+use systems::layout::*;
+use systems::updates::*;
+
+use bevy::prelude::*;
+
+use crate::actions::{Bindings, Rebinding};
+use crate::locale::Locale;
+use crate::settings::Settings;
+
+// The settings menu is an overlay, so it is tracked apart from `AppState`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+pub enum SettingsMenuState {
+    #[default]
+    Closed,
+    Open,
+    // Rebinding page, reached from the settings page
+    Controls,
+}
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<SettingsMenuState>()
+            // OnEnter State Systems
+            .add_systems(OnEnter(SettingsMenuState::Open), spawn_settings_menu)
+            .add_systems(
+                Update,
+                (
+                    interact_with_aiming_mode_button,
+                    interact_with_accuracy_click_button,
+                    interact_with_difficulty_button,
+                    interact_with_minimap_button,
+                    interact_with_ghost_button,
+                    interact_with_window_mode_button,
+                    interact_with_vsync_button,
+                    interact_with_volume_button,
+                    interact_with_colorblind_button,
+                    interact_with_language_button,
+                    interact_with_controls_button,
+                    interact_with_back_button,
+                    activate_settings_menu_button,
+                    (
+                        update_aiming_mode_text,
+                        update_accuracy_click_text,
+                        update_difficulty_text,
+                        update_minimap_text,
+                        update_ghost_text,
+                        update_window_mode_text,
+                        update_vsync_text,
+                        update_volume_text,
+                        update_colorblind_text,
+                        update_language_text,
+                    )
+                        .run_if(
+                            resource_changed::<Settings>().or_else(resource_changed::<Locale>()),
+                        ),
+                )
+                    .run_if(in_state(SettingsMenuState::Open)),
+            )
+            // OnExit State Systems
+            .add_systems(OnExit(SettingsMenuState::Open), despawn_settings_menu)
+            .add_systems(OnEnter(SettingsMenuState::Controls), spawn_controls_menu)
+            .add_systems(
+                Update,
+                (
+                    interact_with_action_binding_buttons,
+                    interact_with_reset_bindings_button,
+                    interact_with_controls_back_button,
+                    activate_controls_menu_button,
+                    update_action_binding_text.run_if(
+                        resource_changed::<Bindings>()
+                            .or_else(resource_changed::<Rebinding>())
+                            .or_else(resource_changed::<Locale>()),
+                    ),
+                )
+                    .run_if(in_state(SettingsMenuState::Controls)),
+            )
+            .add_systems(OnExit(SettingsMenuState::Controls), despawn_controls_menu);
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+pub const BACKGROUND_COLOR: Color = Color::rgb(0.168627, 0.172549, 0.184314);
+
+pub const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+pub const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.35, 0.35);
+
+pub const SETTINGS_MENU_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.position_type = PositionType::Absolute;
+    style.flex_direction = FlexDirection::Column;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Percent(100.0);
+    style.height = Val::Percent(100.0);
+    style.row_gap = Val::Px(8.0);
+    style.column_gap = Val::Px(8.0);
+    style
+};
+
+pub const OPTION_BUTTON_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Px(400.0);
+    style.height = Val::Px(80.0);
+    style.border = UiRect::all(Val::Px(3.0));
+    style
+};
+
+pub const BUTTON_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Px(200.0);
+    style.height = Val::Px(80.0);
+    style.border = UiRect::all(Val::Px(3.0));
+    style
+};
+
+// Two columns of options, so the graphics and audio ones fit next to the gameplay ones
+pub const OPTION_LIST_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.flex_direction = FlexDirection::Row;
+    style.flex_wrap = FlexWrap::Wrap;
+    style.justify_content = JustifyContent::Center;
+    style.width = Val::Px(820.0);
+    style.row_gap = Val::Px(8.0);
+    style.column_gap = Val::Px(8.0);
+    style
+};
+
+// Two columns of bindings, so every action fits on screen
+pub const BINDING_LIST_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.flex_direction = FlexDirection::Row;
+    style.flex_wrap = FlexWrap::Wrap;
+    style.justify_content = JustifyContent::Center;
+    style.width = Val::Px(1300.0);
+    style.row_gap = Val::Px(8.0);
+    style.column_gap = Val::Px(8.0);
+    style
+};
+
+pub const BINDING_BUTTON_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Px(640.0);
+    style.height = Val::Px(50.0);
+    style.border = UiRect::all(Val::Px(3.0));
+    style
+};
+
+pub const FOOTER_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.flex_direction = FlexDirection::Row;
+    style.column_gap = Val::Px(8.0);
+    style.margin = UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Px(16.0), Val::Px(0.0));
+    style
+};
+
+pub fn get_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 64.0,
+        color: Color::rgb(1.0, 1.0, 1.0),
+    }
+}
+
+pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 32.0,
+        color: Color::rgb(1.0, 1.0, 1.0),
+    }
+}
+
+pub fn get_binding_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 24.0,
+        color: Color::rgb(1.0, 1.0, 1.0),
+    }
+}
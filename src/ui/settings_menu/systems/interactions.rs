@@ -0,0 +1,450 @@
+use bevy::prelude::*;
+
+use crate::actions::{Bindings, Rebinding};
+use crate::settings::Settings;
+use crate::ui::focus::ButtonActivated;
+use crate::ui::settings_menu::components::*;
+use crate::ui::settings_menu::styles::*;
+use crate::ui::settings_menu::SettingsMenuState;
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_aiming_mode_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<AimingModeButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.aiming_mode = settings.aiming_mode.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_accuracy_click_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<AccuracyClickButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.accuracy_click = !settings.accuracy_click;
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_back_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<BackButton>),
+    >,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings_menu_next_state.set(SettingsMenuState::Closed);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_difficulty_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<DifficultyButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.difficulty = settings.difficulty.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_minimap_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<MinimapButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.show_minimap = !settings.show_minimap;
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_ghost_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<GhostButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.show_ghost = !settings.show_ghost;
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_window_mode_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<WindowModeButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.window_mode = settings.window_mode.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_vsync_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<VsyncButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.vsync = !settings.vsync;
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_volume_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor, &VolumeButton),
+        Changed<Interaction>,
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color, button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                *settings.volume_mut(button.channel) = settings.next_volume(button.channel);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_colorblind_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ColorblindButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.colorblind_mode = settings.colorblind_mode.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_language_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<LanguageButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings.language = settings.language.next();
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_controls_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ControlsButton>),
+    >,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                settings_menu_next_state.set(SettingsMenuState::Controls);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn activate_settings_menu_button(
+    mut activated_event_reader: EventReader<ButtonActivated>,
+    aiming_mode_query: Query<(), With<AimingModeButton>>,
+    accuracy_click_query: Query<(), With<AccuracyClickButton>>,
+    difficulty_query: Query<(), With<DifficultyButton>>,
+    minimap_query: Query<(), With<MinimapButton>>,
+    ghost_query: Query<(), With<GhostButton>>,
+    window_mode_query: Query<(), With<WindowModeButton>>,
+    vsync_query: Query<(), With<VsyncButton>>,
+    volume_query: Query<&VolumeButton>,
+    colorblind_query: Query<(), With<ColorblindButton>>,
+    language_query: Query<(), With<LanguageButton>>,
+    controls_query: Query<(), With<ControlsButton>>,
+    back_query: Query<(), With<BackButton>>,
+    mut settings: ResMut<Settings>,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+) {
+    for ButtonActivated(entity) in activated_event_reader.read() {
+        if aiming_mode_query.contains(*entity) {
+            settings.aiming_mode = settings.aiming_mode.next();
+        } else if accuracy_click_query.contains(*entity) {
+            settings.accuracy_click = !settings.accuracy_click;
+        } else if difficulty_query.contains(*entity) {
+            settings.difficulty = settings.difficulty.next();
+        } else if minimap_query.contains(*entity) {
+            settings.show_minimap = !settings.show_minimap;
+        } else if ghost_query.contains(*entity) {
+            settings.show_ghost = !settings.show_ghost;
+        } else if window_mode_query.contains(*entity) {
+            settings.window_mode = settings.window_mode.next();
+        } else if vsync_query.contains(*entity) {
+            settings.vsync = !settings.vsync;
+        } else if let Ok(button) = volume_query.get(*entity) {
+            *settings.volume_mut(button.channel) = settings.next_volume(button.channel);
+        } else if colorblind_query.contains(*entity) {
+            settings.colorblind_mode = settings.colorblind_mode.next();
+        } else if language_query.contains(*entity) {
+            settings.language = settings.language.next();
+        } else if controls_query.contains(*entity) {
+            settings_menu_next_state.set(SettingsMenuState::Controls);
+        } else if back_query.contains(*entity) {
+            settings_menu_next_state.set(SettingsMenuState::Closed);
+        }
+    }
+}
+
+// While a binding is being captured, clicks belong to the capture, not the buttons
+#[allow(clippy::type_complexity)]
+pub fn interact_with_action_binding_buttons(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor, &ActionBindingButton),
+        Changed<Interaction>,
+    >,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    let capturing = rebinding.0.is_some();
+    for (interaction, mut color, button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                if !capturing {
+                    rebinding.0 = Some(button.action);
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_reset_bindings_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ResetBindingsButton>),
+    >,
+    mut bindings: ResMut<Bindings>,
+    rebinding: Res<Rebinding>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                if rebinding.0.is_none() {
+                    reset_bindings(&mut bindings);
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interact_with_controls_back_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ControlsBackButton>),
+    >,
+    rebinding: Res<Rebinding>,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                if rebinding.0.is_none() {
+                    settings_menu_next_state.set(SettingsMenuState::Open);
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+pub fn activate_controls_menu_button(
+    mut activated_event_reader: EventReader<ButtonActivated>,
+    action_binding_query: Query<&ActionBindingButton>,
+    reset_query: Query<(), With<ResetBindingsButton>>,
+    back_query: Query<(), With<ControlsBackButton>>,
+    mut bindings: ResMut<Bindings>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings_menu_next_state: ResMut<NextState<SettingsMenuState>>,
+) {
+    for ButtonActivated(entity) in activated_event_reader.read() {
+        if let Ok(button) = action_binding_query.get(*entity) {
+            rebinding.0 = Some(button.action);
+        } else if reset_query.contains(*entity) {
+            reset_bindings(&mut bindings);
+        } else if back_query.contains(*entity) {
+            settings_menu_next_state.set(SettingsMenuState::Open);
+        }
+    }
+}
+
+fn reset_bindings(bindings: &mut Bindings) {
+    *bindings = Bindings::default();
+    bindings.save();
+}
@@ -0,0 +1,627 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::actions::{Action, Bindings, Rebinding};
+use crate::locale::{Locale, Localized};
+use crate::settings::{Settings, VolumeChannel};
+use crate::ui::focus::Focusable;
+use crate::ui::settings_menu::components::*;
+use crate::ui::settings_menu::styles::*;
+
+pub fn spawn_settings_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+) {
+    build_settings_menu(&mut commands, &asset_server, &settings, &locale);
+}
+
+pub fn despawn_settings_menu(
+    mut commands: Commands,
+    settings_menu_query: Query<Entity, With<SettingsMenu>>,
+) {
+    if let Ok(settings_menu_entity) = settings_menu_query.get_single() {
+        commands.entity(settings_menu_entity).despawn_recursive();
+    }
+}
+
+fn on_off(value: bool, locale: &Locale) -> &str {
+    locale.get(if value { "settings.on" } else { "settings.off" })
+}
+
+pub fn aiming_mode_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.aiming", &[locale.get(settings.aiming_mode.key())])
+}
+
+pub fn accuracy_click_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.accuracy_click", &[on_off(settings.accuracy_click, locale)])
+}
+
+pub fn difficulty_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.difficulty", &[locale.get(settings.difficulty.key())])
+}
+
+pub fn minimap_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.minimap", &[on_off(settings.show_minimap, locale)])
+}
+
+pub fn ghost_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.ghost", &[on_off(settings.show_ghost, locale)])
+}
+
+pub fn window_mode_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.window", &[locale.get(settings.window_mode.key())])
+}
+
+pub fn vsync_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.vsync", &[on_off(settings.vsync, locale)])
+}
+
+pub fn volume_label(settings: &Settings, channel: VolumeChannel, locale: &Locale) -> String {
+    locale.format(channel.key(), &[&settings.volume(channel).to_string()])
+}
+
+pub fn colorblind_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.colorblind", &[locale.get(settings.colorblind_mode.key())])
+}
+
+pub fn language_label(settings: &Settings, locale: &Locale) -> String {
+    locale.format("settings.language", &[settings.language.label()])
+}
+
+pub fn build_settings_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    settings: &Settings,
+    locale: &Locale,
+) -> Entity {
+    let settings_menu_entity = commands
+        .spawn((
+            NodeBundle {
+                style: SETTINGS_MENU_STYLE,
+                background_color: BACKGROUND_COLOR.into(),
+                // Drawn over whichever menu opened it and swallows its clicks
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            SettingsMenu {},
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                TextBundle {
+                    text: Text {
+                        sections: vec![TextSection::new(
+                            locale.get("settings.title"),
+                            get_title_text_style(asset_server),
+                        )],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Localized { key: "settings.title" },
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: OPTION_LIST_STYLE,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    // Aiming Mode Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            AimingModeButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            aiming_mode_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                AimingModeText {},
+                            ));
+                        });
+                    // Accuracy Click Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            AccuracyClickButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            accuracy_click_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                AccuracyClickText {},
+                            ));
+                        });
+                    // Difficulty Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            DifficultyButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            difficulty_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                DifficultyText {},
+                            ));
+                        });
+                    // Minimap Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MinimapButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            minimap_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                MinimapText {},
+                            ));
+                        });
+                    // Ghost Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            GhostButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            ghost_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                GhostText {},
+                            ));
+                        });
+                    // Window Mode Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            WindowModeButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            window_mode_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                WindowModeText {},
+                            ));
+                        });
+                    // VSync Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            VsyncButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            vsync_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                VsyncText {},
+                            ));
+                        });
+                    // Volume Buttons, master first
+                    for channel in VolumeChannel::ALL {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: OPTION_BUTTON_STYLE,
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                VolumeButton { channel },
+                                Focusable,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle {
+                                        text: Text {
+                                            sections: vec![TextSection::new(
+                                                volume_label(settings, channel, locale),
+                                                get_button_text_style(asset_server),
+                                            )],
+                                            alignment: TextAlignment::Center,
+                                            ..default()
+                                        },
+                                        ..default()
+                                    },
+                                    VolumeText { channel },
+                                ));
+                            });
+                    }
+                    // Colorblind Mode Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            ColorblindButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            colorblind_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                ColorblindText {},
+                            ));
+                        });
+                    // Language Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            LanguageButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            language_label(settings, locale),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                LanguageText {},
+                            ));
+                        });
+                });
+            parent
+                .spawn(NodeBundle {
+                    style: FOOTER_STYLE,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    // Controls Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            ControlsButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            locale.get("settings.controls"),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                Localized { key: "settings.controls" },
+                            ));
+                        });
+                    // Back Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            BackButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            locale.get("menu.back"),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                Localized { key: "menu.back" },
+                            ));
+                        });
+                });
+        })
+        .id();
+
+    settings_menu_entity
+}
+
+pub fn spawn_controls_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bindings: Res<Bindings>,
+    rebinding: Res<Rebinding>,
+    locale: Res<Locale>,
+) {
+    build_controls_menu(&mut commands, &asset_server, &bindings, &rebinding, &locale);
+}
+
+pub fn despawn_controls_menu(
+    mut commands: Commands,
+    controls_menu_query: Query<Entity, With<ControlsMenu>>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    // Leaving the page gives up on a pending rebind
+    rebinding.0 = None;
+    if let Ok(controls_menu_entity) = controls_menu_query.get_single() {
+        commands.entity(controls_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn action_binding_label(
+    action: Action,
+    bindings: &Bindings,
+    rebinding: &Rebinding,
+    locale: &Locale,
+) -> String {
+    if rebinding.0 == Some(action) {
+        return locale.format("controls.waiting", &[locale.get(action.key())]);
+    }
+    let bound: Vec<String> = bindings
+        .get(action)
+        .iter()
+        .map(|binding| binding.label(locale))
+        .collect();
+    locale.format("controls.binding", &[locale.get(action.key()), &bound.join(", ")])
+}
+
+pub fn build_controls_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    bindings: &Bindings,
+    rebinding: &Rebinding,
+    locale: &Locale,
+) -> Entity {
+    let controls_menu_entity = commands
+        .spawn((
+            NodeBundle {
+                style: SETTINGS_MENU_STYLE,
+                background_color: BACKGROUND_COLOR.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            ControlsMenu {},
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                TextBundle {
+                    text: Text {
+                        sections: vec![TextSection::new(
+                            locale.get("controls.title"),
+                            get_title_text_style(asset_server),
+                        )],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Localized { key: "controls.title" },
+            ));
+            // One button per action, pressing it waits for the new binding
+            parent
+                .spawn(NodeBundle {
+                    style: BINDING_LIST_STYLE,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for action in Action::ALL {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: BINDING_BUTTON_STYLE,
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                ActionBindingButton { action },
+                                Focusable,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle {
+                                        text: Text {
+                                            sections: vec![TextSection::new(
+                                                action_binding_label(action, bindings, rebinding, locale),
+                                                get_binding_text_style(asset_server),
+                                            )],
+                                            alignment: TextAlignment::Center,
+                                            ..default()
+                                        },
+                                        ..default()
+                                    },
+                                    ActionBindingText { action },
+                                ));
+                            });
+                    }
+                });
+            parent
+                .spawn(NodeBundle {
+                    style: FOOTER_STYLE,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    // Reset Defaults Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: OPTION_BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            ResetBindingsButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            locale.get("controls.reset"),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                Localized { key: "controls.reset" },
+                            ));
+                        });
+                    // Back Button
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: BUTTON_STYLE,
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            ControlsBackButton {},
+                            Focusable,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text {
+                                        sections: vec![TextSection::new(
+                                            locale.get("menu.back"),
+                                            get_button_text_style(asset_server),
+                                        )],
+                                        alignment: TextAlignment::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                Localized { key: "menu.back" },
+                            ));
+                        });
+                });
+        })
+        .id();
+
+    controls_menu_entity
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use crate::actions::{Bindings, Rebinding};
+use crate::locale::Locale;
+use crate::settings::Settings;
+use crate::ui::settings_menu::components::{
+    AccuracyClickText, ActionBindingText, AimingModeText, ColorblindText, DifficultyText,
+    GhostText, LanguageText, MinimapText, VolumeText, VsyncText, WindowModeText,
+};
+use crate::ui::settings_menu::systems::layout::{
+    accuracy_click_label, action_binding_label, aiming_mode_label, colorblind_label,
+    difficulty_label, ghost_label, language_label, minimap_label, volume_label, vsync_label,
+    window_mode_label,
+};
+
+pub fn update_aiming_mode_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<AimingModeText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = aiming_mode_label(&settings, &locale);
+    }
+}
+
+pub fn update_accuracy_click_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<AccuracyClickText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = accuracy_click_label(&settings, &locale);
+    }
+}
+
+pub fn update_difficulty_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<DifficultyText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = difficulty_label(&settings, &locale);
+    }
+}
+
+pub fn update_minimap_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<MinimapText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = minimap_label(&settings, &locale);
+    }
+}
+
+pub fn update_ghost_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<GhostText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = ghost_label(&settings, &locale);
+    }
+}
+
+pub fn update_window_mode_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<WindowModeText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = window_mode_label(&settings, &locale);
+    }
+}
+
+pub fn update_vsync_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<VsyncText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = vsync_label(&settings, &locale);
+    }
+}
+
+pub fn update_volume_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<(&mut Text, &VolumeText)>,
+) {
+    for (mut text, volume_text) in text_query.iter_mut() {
+        text.sections[0].value = volume_label(&settings, volume_text.channel, &locale);
+    }
+}
+
+pub fn update_colorblind_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<ColorblindText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = colorblind_label(&settings, &locale);
+    }
+}
+
+pub fn update_language_text(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<LanguageText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = language_label(&settings, &locale);
+    }
+}
+
+pub fn update_action_binding_text(
+    bindings: Res<Bindings>,
+    rebinding: Res<Rebinding>,
+    locale: Res<Locale>,
+    mut text_query: Query<(&mut Text, &ActionBindingText)>,
+) {
+    for (mut text, binding_text) in text_query.iter_mut() {
+        text.sections[0].value = action_binding_label(binding_text.action, &bindings, &rebinding, &locale);
+    }
+}
@@ -0,0 +1,61 @@
+// Labels built from the string tables, checked in every language so a key missing
+// from one of them doesn't show up on screen as the key itself.
+
+use bevy::prelude::*;
+
+use golf_2d_bevy::actions::{Action, Binding, Bindings};
+use golf_2d_bevy::locale::{Language, Locale};
+use golf_2d_bevy::settings::VolumeChannel;
+
+const LANGUAGES: [Language; 2] = [Language::EnUs, Language::PtBr];
+
+#[test]
+fn every_binding_is_named_in_both_languages() {
+    let bindings = Bindings::default();
+    let mut bound: Vec<Binding> = Action::ALL
+        .iter()
+        .flat_map(|action| bindings.get(*action).to_vec())
+        .collect();
+    bound.push(Binding::Mouse(MouseButton::Other(4)));
+    bound.push(Binding::Gamepad(GamepadButtonType::Other(7)));
+
+    for language in LANGUAGES {
+        let locale = Locale::new(language);
+        for binding in &bound {
+            let label = binding.label(&locale);
+            // A missing key shows up as the key itself
+            assert!(
+                !["binding.", "mouse.", "pad."]
+                    .iter()
+                    .any(|key| label.contains(key)),
+                "{:?} {}",
+                language,
+                label
+            );
+        }
+    }
+    let english = Locale::new(Language::EnUs);
+    assert_eq!(
+        Binding::Gamepad(GamepadButtonType::South).label(&english),
+        "Pad Bottom Button"
+    );
+    assert_eq!(
+        Binding::Mouse(MouseButton::Other(4)).label(&english),
+        "Mouse Button 4"
+    );
+}
+
+#[test]
+fn every_volume_is_named_in_both_languages() {
+    for language in LANGUAGES {
+        let locale = Locale::new(language);
+        for channel in VolumeChannel::ALL {
+            assert_ne!(locale.get(channel.key()), channel.key(), "{:?}", language);
+        }
+    }
+    let english = Locale::new(Language::EnUs);
+    assert_eq!(
+        english.format(VolumeChannel::Music.key(), &["40"]),
+        "Music: 40%"
+    );
+}
@@ -0,0 +1,249 @@
+// Online rounds: the relay on its own, then two headless games playing through a
+// relay on localhost.
+
+use std::net::{SocketAddr, UdpSocket};
+use std::thread;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+use golf_2d_bevy::game::aiming::Swing;
+use golf_2d_bevy::game::components::Ball;
+use golf_2d_bevy::game::players::{ActiveBall, Player};
+use golf_2d_bevy::game::{physics, GameState, SimulationPlugin};
+use golf_2d_bevy::net::client::TIMEOUT_SECONDS;
+use golf_2d_bevy::net::protocol::{decode, encode, ClientMessage, RelayMessage, MAX_DATAGRAM};
+use golf_2d_bevy::net::relay::{self, Relay};
+use golf_2d_bevy::net::{Disconnected, NetPlugin, NetSession};
+use golf_2d_bevy::AppState;
+
+const MAX_STEPS: usize = 5000;
+
+fn address(port: u16) -> SocketAddr {
+    ([127, 0, 0, 1], port).into()
+}
+
+#[test]
+fn the_relay_starts_the_round_once_every_seat_is_taken() {
+    let mut relay = Relay::new(2);
+
+    assert_eq!(
+        relay.handle(address(1), ClientMessage::Join),
+        vec![(address(1), RelayMessage::Welcome { player: 0 })]
+    );
+    // Joining again just repeats the seat
+    assert_eq!(
+        relay.handle(address(1), ClientMessage::Join),
+        vec![(address(1), RelayMessage::Welcome { player: 0 })]
+    );
+    assert_eq!(
+        relay.handle(address(2), ClientMessage::Join),
+        vec![
+            (address(2), RelayMessage::Welcome { player: 1 }),
+            (address(1), RelayMessage::Start { players: 2 }),
+            (address(2), RelayMessage::Start { players: 2 }),
+        ]
+    );
+    assert_eq!(
+        relay.handle(address(3), ClientMessage::Join),
+        vec![(address(3), RelayMessage::Full)]
+    );
+}
+
+#[test]
+fn the_relay_takes_every_shot_once_in_arrival_order() {
+    let mut relay = Relay::new(2);
+    relay.handle(address(1), ClientMessage::Join);
+    relay.handle(address(2), ClientMessage::Join);
+    let shot = |seq| ClientMessage::Shot {
+        seq,
+        hole: 1,
+        velocity: Vec2::new(100.0, 0.0),
+    };
+
+    assert_eq!(relay.handle(address(2), shot(0)).len(), 2);
+    // A resent shot only goes back to its sender
+    let repeated = relay.handle(address(2), shot(0));
+    assert_eq!(repeated.len(), 1);
+    assert_eq!(repeated[0].0, address(2));
+    // Out of order, dropped until the one before it arrives
+    assert!(relay.handle(address(1), shot(1)).is_empty());
+    relay.handle(address(1), shot(0));
+    // Strangers can't shoot
+    assert!(relay.handle(address(3), shot(0)).is_empty());
+
+    let order: Vec<(u32, usize)> = relay
+        .shots()
+        .iter()
+        .map(|shot| (shot.order, shot.player))
+        .collect();
+    assert_eq!(order, vec![(0, 1), (1, 0)]);
+}
+
+#[test]
+fn the_relay_answers_every_resend() {
+    let mut relay = Relay::new(1);
+    relay.handle(address(1), ClientMessage::Join);
+
+    assert_eq!(
+        relay.handle(address(1), ClientMessage::Resend { next: 0 }),
+        vec![(address(1), RelayMessage::Alive)]
+    );
+}
+
+#[test]
+fn messages_survive_the_wire() {
+    let message = ClientMessage::Shot {
+        seq: 3,
+        hole: 2,
+        velocity: Vec2::new(1.0 / 3.0, -0.0),
+    };
+
+    assert_eq!(decode::<ClientMessage>(&encode(&message)), Some(message));
+    assert_eq!(decode::<ClientMessage>(b"not a message"), None);
+}
+
+fn start_relay(players: usize) -> SocketAddr {
+    let socket = UdpSocket::bind(address(0)).unwrap();
+    let relay_address = socket.local_addr().unwrap();
+    thread::spawn(move || relay::serve(socket, players));
+    relay_address
+}
+
+fn online_app(relay: SocketAddr) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        SimulationPlugin,
+        NetPlugin { relay: Some(relay) },
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        1.0 / physics::TICKS_PER_SECOND,
+    )));
+    app.update();
+    app
+}
+
+fn game_state(app: &App) -> GameState {
+    *app.world.resource::<State<GameState>>().get()
+}
+
+fn app_state(app: &App) -> AppState {
+    *app.world.resource::<State<AppState>>().get()
+}
+
+fn seat(app: &App) -> usize {
+    app.world.resource::<NetSession>().client.player.unwrap()
+}
+
+// Runs both games side by side, giving the relay a moment between frames
+fn step_until(apps: &mut [App; 2], condition: impl Fn(&[App; 2]) -> bool) {
+    for _ in 0..MAX_STEPS {
+        if condition(apps) {
+            return;
+        }
+        for app in apps.iter_mut() {
+            app.update();
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    panic!("gave up after {} steps", MAX_STEPS);
+}
+
+// There is no camera to fly over the hole, so the ball is handed over right away
+fn skip_flyovers(apps: &mut [App; 2]) {
+    step_until(apps, |apps| {
+        apps.iter().all(|app| game_state(app) == GameState::Flyover)
+    });
+    for app in apps.iter_mut() {
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::DeadBall);
+    }
+    step_until(apps, |apps| {
+        apps.iter()
+            .all(|app| game_state(app) == GameState::DeadBall)
+    });
+}
+
+fn ball_positions(app: &mut App) -> Vec<Vec2> {
+    let mut balls: Vec<(Player, Vec2)> = app
+        .world
+        .query_filtered::<(&Player, &Transform), With<Ball>>()
+        .iter(&app.world)
+        .map(|(player, transform)| (*player, transform.translation.truncate()))
+        .collect();
+    balls.sort_by_key(|(player, _)| player.0);
+    balls.into_iter().map(|(_, position)| position).collect()
+}
+
+fn active_player(app: &mut App) -> Player {
+    *app.world
+        .query_filtered::<&Player, With<ActiveBall>>()
+        .single(&app.world)
+}
+
+#[test]
+fn a_shot_taken_on_one_machine_rolls_the_same_on_the_other() {
+    let relay = start_relay(2);
+    let mut apps = [online_app(relay), online_app(relay)];
+
+    step_until(&mut apps, |apps| {
+        apps.iter().all(|app| app_state(app) == AppState::Game)
+    });
+    skip_flyovers(&mut apps);
+    apps.sort_by_key(seat);
+
+    apps[0].world.send_event(Swing {
+        velocity: Vec2::new(450.0, -320.0),
+    });
+    step_until(&mut apps, |apps| {
+        apps.iter()
+            .all(|app| game_state(app) == GameState::BallMoving)
+    });
+    step_until(&mut apps, |apps| {
+        apps.iter()
+            .all(|app| game_state(app) == GameState::DeadBall)
+    });
+
+    let [first, second] = &mut apps;
+    assert_eq!(ball_positions(first), ball_positions(second));
+    assert_ne!(ball_positions(first)[0], ball_positions(first)[1]);
+    assert_eq!(active_player(first), Player(1));
+    assert_eq!(active_player(second), Player(1));
+}
+
+#[test]
+fn a_silent_relay_sends_the_game_back_to_the_main_menu() {
+    // Stands in for a relay that seats one player and then goes away
+    let relay = UdpSocket::bind(address(0)).unwrap();
+    let mut app = online_app(relay.local_addr().unwrap());
+    let mut buffer = [0; MAX_DATAGRAM];
+    let (_, client) = relay.recv_from(&mut buffer).unwrap();
+    for message in [
+        RelayMessage::Welcome { player: 0 },
+        RelayMessage::Start { players: 1 },
+    ] {
+        relay.send_to(&encode(&message), client).unwrap();
+    }
+
+    let mut steps = 0;
+    while app_state(&app) != AppState::Game {
+        assert!(steps < MAX_STEPS, "the round never started");
+        app.update();
+        thread::sleep(Duration::from_millis(1));
+        steps += 1;
+    }
+    let started = steps;
+    while app_state(&app) != AppState::MainMenu {
+        assert!(steps < MAX_STEPS, "the game never gave up on the relay");
+        app.update();
+        steps += 1;
+    }
+
+    let waited = (steps - started) as f64 / physics::TICKS_PER_SECOND;
+    assert!(waited >= TIMEOUT_SECONDS, "gave up after {}s", waited);
+    assert!(!app.world.contains_resource::<NetSession>());
+    assert!(app.world.contains_resource::<Disconnected>());
+}
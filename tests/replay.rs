@@ -0,0 +1,33 @@
+// Replays on disk. Saving writes under `config/` in the working directory, so this
+// runs from a scratch directory in its own test binary.
+
+use std::env;
+use std::fs;
+
+use golf_2d_bevy::game::replay::{Replay, KEPT_REPLAYS};
+
+#[test]
+fn only_the_newest_replays_are_kept() {
+    let directory = env::temp_dir().join(format!("golf-replays-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    env::set_current_dir(&directory).unwrap();
+
+    for players in 1..=KEPT_REPLAYS + 5 {
+        Replay {
+            players,
+            ..Replay::default()
+        }
+        .save();
+    }
+
+    let mut names: Vec<String> = fs::read_dir("config/replays")
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names.len(), KEPT_REPLAYS);
+    assert_eq!(names[0], "round-0006.ron");
+    assert_eq!(Replay::load_latest().players, KEPT_REPLAYS + 5);
+
+    let _ = fs::remove_dir_all(&directory);
+}
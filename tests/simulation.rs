@@ -0,0 +1,914 @@
+// Plays holes headless: `SimulationPlugin` under `MinimalPlugins`, with a clock that
+// advances exactly one fixed timestep per update.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+use golf_2d_bevy::actions::ActionsPlugin;
+use golf_2d_bevy::camera::follow::CameraRig;
+use golf_2d_bevy::game::ai;
+use golf_2d_bevy::game::aiming::Swing;
+use golf_2d_bevy::game::components::{Ball, Collider, CollisionEvent, GolfHole, Water};
+use golf_2d_bevy::game::ghost::PersonalBests;
+use golf_2d_bevy::game::levels::layout::LevelLayout;
+use golf_2d_bevy::game::levels::{Course, GeneratedHoles, HolePar, Level, LevelBounds, LAST_LEVEL};
+use golf_2d_bevy::game::physics;
+use golf_2d_bevy::game::players::{ActiveBall, Holed, Player, Players};
+use golf_2d_bevy::game::replay::{Playback, Replay, Shot};
+use golf_2d_bevy::game::retry::RetryPlugin;
+use golf_2d_bevy::game::swings_count::Scoreboard;
+use golf_2d_bevy::game::systems::wall_from_transform;
+use golf_2d_bevy::game::{GameState, PauseState, SimulationPlugin};
+use golf_2d_bevy::settings::{Difficulty, Settings};
+use golf_2d_bevy::ui::minimap::components::{Minimap, MinimapBall};
+use golf_2d_bevy::ui::minimap::MinimapPlugin;
+use golf_2d_bevy::{AppState, GameOver, BALL_SIZE};
+
+// Plenty for any shot in the test holes to stop
+const MAX_STEPS: usize = 2000;
+// Generated holes are built off the main thread, slowly in a debug build
+const MAX_WAIT: Duration = Duration::from_secs(60);
+
+// Where level 1 puts the ball
+const LEVEL_1_BALL: Vec2 = Vec2::new(-370.0, -100.0);
+
+fn headless_app() -> App {
+    headless_app_at(Duration::from_secs_f64(1.0 / physics::TICKS_PER_SECOND))
+}
+
+// `frame` is how much time every update advances, like a game running at 1 / frame FPS
+fn headless_app_at(frame: Duration) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SimulationPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(frame));
+    app.update();
+    app
+}
+
+fn game_state(app: &App) -> GameState {
+    *app.world.resource::<State<GameState>>().get()
+}
+
+fn app_state(app: &App) -> AppState {
+    *app.world.resource::<State<AppState>>().get()
+}
+
+fn step_until(app: &mut App, condition: impl Fn(&App) -> bool) {
+    for _ in 0..MAX_STEPS {
+        if condition(app) {
+            return;
+        }
+        app.update();
+    }
+    panic!("gave up after {} steps", MAX_STEPS);
+}
+
+// Like `step_until`, for what takes real time rather than updates
+fn wait_until(app: &mut App, condition: impl Fn(&App) -> bool) {
+    let started = Instant::now();
+    while !condition(app) {
+        assert!(started.elapsed() < MAX_WAIT, "gave up after {:?}", MAX_WAIT);
+        thread::sleep(Duration::from_millis(5));
+        app.update();
+    }
+}
+
+// There is no camera to fly over the hole, so the ball is handed over right away
+fn skip_flyover(app: &mut App) {
+    step_until(app, |app| game_state(app) == GameState::Flyover);
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::DeadBall);
+    step_until(app, |app| game_state(app) == GameState::DeadBall);
+}
+
+fn start_round(app: &mut App, level: i32) {
+    app.world.resource_mut::<Level>().0 = level;
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Game);
+    skip_flyover(app);
+}
+
+// The swing is taken during one update and the ball starts rolling on the next
+fn shoot(app: &mut App, velocity: Vec2) {
+    app.world.send_event(Swing { velocity });
+    step_until(app, |app| game_state(app) == GameState::BallMoving);
+}
+
+// The ball whose turn it is
+fn ball_position(app: &mut App) -> Vec2 {
+    app.world
+        .query_filtered::<&Transform, With<ActiveBall>>()
+        .single(&app.world)
+        .translation
+        .truncate()
+}
+
+fn place_ball(app: &mut App, position: Vec2) {
+    let mut transform = app
+        .world
+        .query_filtered::<&mut Transform, With<ActiveBall>>()
+        .single_mut(&mut app.world);
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+}
+
+fn walls(app: &mut App) -> Vec<physics::Wall> {
+    let transforms: Vec<Transform> = app
+        .world
+        .query_filtered::<&Transform, With<Collider>>()
+        .iter(&app.world)
+        .copied()
+        .collect();
+    physics::sorted_walls(transforms.iter().map(wall_from_transform))
+}
+
+fn record_shot(app: &mut App, velocity: Vec2) -> Vec<[u32; 2]> {
+    shoot(app, velocity);
+    positions_until_rest(app)
+}
+
+// Ball position after every update until it stops, as raw bits so -0.0 and 0.0 differ
+fn positions_until_rest(app: &mut App) -> Vec<[u32; 2]> {
+    let mut positions = Vec::new();
+    for _ in 0..MAX_STEPS {
+        let position = ball_position(app);
+        positions.push([position.x.to_bits(), position.y.to_bits()]);
+        if game_state(app) == GameState::DeadBall {
+            return positions;
+        }
+        app.update();
+    }
+    panic!("gave up after {} steps", MAX_STEPS);
+}
+
+fn hole_position(app: &mut App) -> Vec2 {
+    app.world
+        .query_filtered::<&Transform, With<GolfHole>>()
+        .single(&app.world)
+        .translation
+        .truncate()
+}
+
+#[test]
+fn loading_a_hole_spawns_its_ball_hole_and_walls() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+
+    assert_eq!(ball_position(&mut app), LEVEL_1_BALL);
+    assert_eq!(hole_position(&mut app), Vec2::new(350.0, 100.0));
+    let walls = app
+        .world
+        .query_filtered::<(), With<Collider>>()
+        .iter(&app.world)
+        .count();
+    assert_eq!(walls, 5);
+    let bounds = app.world.resource::<LevelBounds>().0;
+    assert!(bounds.contains(LEVEL_1_BALL));
+}
+
+#[test]
+fn a_loaded_hole_reads_back_as_a_layout() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+
+    let layout = LevelLayout::from_world(&mut app.world).unwrap();
+    assert_eq!(layout.ball, LEVEL_1_BALL);
+    assert_eq!(layout.hole, hole_position(&mut app));
+    assert_eq!(layout.walls.len(), 5);
+    assert_eq!(layout.bounds(), app.world.resource::<LevelBounds>().0);
+}
+
+#[test]
+fn a_swing_rolls_the_ball_and_counts_a_stroke() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    assert_eq!(game_state(&app), GameState::BallMoving);
+    let card = app.world.resource::<Scoreboard>().card(Player(0));
+    assert_eq!(card.score, 1);
+    assert_eq!(card.holes[0].strokes, 1);
+
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    let position = ball_position(&mut app);
+    assert!(
+        position.x > LEVEL_1_BALL.x + 100.0,
+        "ball stopped at {}",
+        position
+    );
+    assert_eq!(position.y, LEVEL_1_BALL.y);
+}
+
+#[test]
+fn the_ball_bounces_off_walls() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+    let mut collision_reader = ManualEventReader::<CollisionEvent>::default();
+    let mut normals = Vec::new();
+
+    // Straight down into the bottom wall
+    shoot(&mut app, Vec2::new(0.0, -400.0));
+    for _ in 0..MAX_STEPS {
+        let events = app.world.resource::<Events<CollisionEvent>>();
+        normals.extend(collision_reader.read(events).map(|event| event.normal));
+        if game_state(&app) == GameState::DeadBall {
+            break;
+        }
+        app.update();
+    }
+
+    assert!(normals.contains(&Vec2::Y), "normals: {:?}", normals);
+    let position = ball_position(&mut app);
+    assert!(
+        position.y > -135.0 && position.y < -15.0,
+        "ball stopped at {}",
+        position
+    );
+}
+
+#[test]
+fn sinking_the_ball_loads_the_next_hole() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+
+    let hole = hole_position(&mut app);
+    place_ball(&mut app, hole - Vec2::new(40.0, 0.0));
+    shoot(&mut app, Vec2::new(200.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::Flyover);
+
+    assert_eq!(app.world.resource::<Level>().0, 2);
+    let card = app.world.resource::<Scoreboard>().card(Player(0));
+    assert_eq!(card.score, 1);
+    assert_eq!(card.holes[0].strokes, 1);
+}
+
+#[test]
+fn restarting_a_hole_takes_back_its_strokes() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::RestartingHole);
+    skip_flyover(&mut app);
+
+    assert_eq!(ball_position(&mut app), LEVEL_1_BALL);
+    let card = app.world.resource::<Scoreboard>().card(Player(0));
+    assert_eq!(card.score, 0);
+    assert_eq!(card.holes[0].strokes, 0);
+}
+
+#[test]
+fn finishing_the_last_hole_ends_the_round() {
+    let mut app = headless_app();
+    start_round(&mut app, LAST_LEVEL);
+    let mut game_over_reader = ManualEventReader::<GameOver>::default();
+
+    let hole = hole_position(&mut app);
+    place_ball(&mut app, hole - Vec2::new(40.0, 0.0));
+    shoot(&mut app, Vec2::new(200.0, 0.0));
+    step_until(&mut app, |app| app_state(app) == AppState::GameOver);
+    app.update();
+
+    assert_eq!(game_state(&app), GameState::OutOfGame);
+    let events = app.world.resource::<Events<GameOver>>();
+    let final_scores: Vec<Vec<usize>> = game_over_reader
+        .read(events)
+        .map(|game_over| game_over.scores.iter().map(|score| score.score).collect())
+        .collect();
+    assert_eq!(final_scores, vec![vec![1]]);
+}
+
+#[test]
+fn the_same_shot_always_rolls_the_same_way() {
+    // Down and right, so it bounces off the bottom wall on the way
+    let velocity = Vec2::new(450.0, -320.0);
+    let mut first = headless_app();
+    start_round(&mut first, 1);
+    let mut second = headless_app();
+    start_round(&mut second, 1);
+
+    assert_eq!(
+        record_shot(&mut first, velocity),
+        record_shot(&mut second, velocity)
+    );
+}
+
+#[test]
+fn the_frame_rate_does_not_change_where_the_ball_stops() {
+    let velocity = Vec2::new(450.0, -320.0);
+    let mut stops = Vec::new();
+    for fps in [64.0, 30.0, 144.0] {
+        let mut app = headless_app_at(Duration::from_secs_f64(1.0 / fps));
+        start_round(&mut app, 1);
+        let positions = record_shot(&mut app, velocity);
+        stops.push(*positions.last().unwrap());
+    }
+
+    assert_eq!(stops[0], stops[1]);
+    assert_eq!(stops[0], stops[2]);
+}
+
+#[test]
+fn the_preview_ends_where_the_ball_stops() {
+    let velocity = Vec2::new(450.0, -320.0);
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+
+    let walls = walls(&mut app);
+    let path = physics::simulate_shot(
+        LEVEL_1_BALL.extend(0.0),
+        golf_2d_bevy::BALL_SIZE.truncate(),
+        velocity,
+        &walls,
+        usize::MAX,
+    );
+    let positions = record_shot(&mut app, velocity);
+    let stop = positions.last().unwrap();
+
+    assert_eq!(
+        path.last().unwrap(),
+        &Vec2::new(f32::from_bits(stop[0]), f32::from_bits(stop[1]))
+    );
+}
+
+#[test]
+fn the_preview_stops_right_after_the_last_bounce() {
+    let wall = physics::Wall {
+        center: Vec3::new(200.0, 0.0, 0.0),
+        size: Vec2::new(30.0, 400.0),
+    };
+    let path = physics::simulate_shot(
+        Vec3::ZERO,
+        golf_2d_bevy::BALL_SIZE.truncate(),
+        Vec2::new(600.0, 0.0),
+        &[wall],
+        1,
+    );
+    // Straight at the wall, so the path ends touching it instead of rolling back
+    let last = path.last().unwrap();
+    assert!(last.x > 150.0, "{:?}", last);
+    assert!(path.windows(2).all(|pair| pair[1].x >= pair[0].x));
+}
+
+#[test]
+fn sliding_along_a_wall_is_not_a_bounce() {
+    let size = golf_2d_bevy::BALL_SIZE.truncate();
+    // Overlapping the bottom of a long wall, so the ball touches it on every tick
+    let ceiling = physics::Wall {
+        center: Vec3::new(100.0, size.y / 2.0 + 13.0, 0.0),
+        size: Vec2::new(400.0, 30.0),
+    };
+    let end = physics::Wall {
+        center: Vec3::new(400.0, 0.0, 0.0),
+        size: Vec2::new(30.0, 400.0),
+    };
+    let path = physics::simulate_shot(Vec3::ZERO, size, Vec2::new(600.0, 0.0), &[ceiling, end], 1);
+    let last = path.last().unwrap();
+    assert!(last.x > 350.0, "{:?}", last);
+    assert_eq!(last.y, 0.0);
+}
+
+// Leaves the round for the replay of it, and waits until the first shot is taken
+fn watch_replay(app: &mut App) {
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Replay);
+    skip_flyover(app);
+    step_until(app, |app| game_state(app) == GameState::BallMoving);
+}
+
+#[test]
+fn every_swing_of_a_round_is_recorded() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    let stop = ball_position(&mut app);
+    shoot(&mut app, Vec2::new(0.0, 150.0));
+
+    let shots = &app.world.resource::<Replay>().shots;
+    assert_eq!(shots.len(), 2);
+    assert_eq!(shots[0].hole, 1);
+    assert_eq!(shots[0].start, LEVEL_1_BALL);
+    assert_eq!(shots[0].velocity, Vec2::new(300.0, 0.0));
+    assert_eq!(shots[1].start, stop);
+    assert!(shots[1].tick > shots[0].tick);
+}
+
+#[test]
+fn replay_files_keep_shots_bit_identical() {
+    let replay = Replay {
+        players: 2,
+        computer: None,
+        course: Course::Endless { seed: u64::MAX },
+        shots: vec![Shot {
+            player: 1,
+            hole: 2,
+            start: Vec2::new(-370.1234, 0.1 + 0.2),
+            velocity: Vec2::new(1.0 / 3.0, -f32::MIN_POSITIVE),
+            tick: 12345,
+            others: vec![(0, Vec2::new(-0.0, 1e-7))],
+        }],
+    };
+    let contents = ron::ser::to_string(&replay).unwrap();
+
+    assert_eq!(ron::from_str::<Replay>(&contents).unwrap(), replay);
+}
+
+#[test]
+fn a_replay_rolls_the_ball_exactly_as_recorded() {
+    let velocity = Vec2::new(450.0, -320.0);
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+    let recorded = record_shot(&mut app, velocity);
+
+    watch_replay(&mut app);
+    let replayed = positions_until_rest(&mut app);
+
+    assert_eq!(app_state(&app), AppState::Replay);
+    assert_eq!(replayed, recorded);
+}
+
+#[test]
+fn a_replay_of_the_last_hole_does_not_end_the_game_again() {
+    let mut app = headless_app();
+    start_round(&mut app, LAST_LEVEL);
+    let hole = hole_position(&mut app);
+    place_ball(&mut app, hole - Vec2::new(40.0, 0.0));
+    shoot(&mut app, Vec2::new(200.0, 0.0));
+    step_until(&mut app, |app| app_state(app) == AppState::GameOver);
+
+    watch_replay(&mut app);
+    step_until(&mut app, |app| game_state(app) == GameState::OutOfGame);
+    app.update();
+
+    assert_eq!(app_state(&app), AppState::Replay);
+}
+
+#[test]
+fn sinking_a_hole_keeps_only_the_strokes_that_count_as_personal_best() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+
+    // Taken back by restarting the hole
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::RestartingHole);
+    skip_flyover(&mut app);
+
+    let hole = hole_position(&mut app);
+    let start = hole - Vec2::new(40.0, 0.0);
+    place_ball(&mut app, start);
+    shoot(&mut app, Vec2::new(200.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::Flyover);
+
+    let best = &app.world.resource::<PersonalBests>().holes[&1];
+    assert_eq!(best.len(), 1);
+    assert_eq!(best[0].start, start);
+    assert_eq!(best[0].velocity, Vec2::new(200.0, 0.0));
+}
+
+fn start_hot_seat(app: &mut App, players: usize, level: i32) {
+    app.world.resource_mut::<Players>().count = players;
+    start_round(app, level);
+}
+
+fn active_player(app: &mut App) -> Player {
+    *app.world
+        .query_filtered::<&Player, With<ActiveBall>>()
+        .single(&app.world)
+}
+
+// Every ball on the course in player order
+fn ball_positions(app: &mut App) -> Vec<Vec2> {
+    let mut balls: Vec<(Player, Vec2)> = app
+        .world
+        .query_filtered::<(&Player, &Transform), With<Ball>>()
+        .iter(&app.world)
+        .map(|(player, transform)| (*player, transform.translation.truncate()))
+        .collect();
+    balls.sort_by_key(|(player, _)| player.0);
+    balls.into_iter().map(|(_, position)| position).collect()
+}
+
+// Rolls the ball whose turn it is into the cup
+fn sink_active_ball(app: &mut App) {
+    let hole = hole_position(app);
+    place_ball(app, hole - Vec2::new(40.0, 0.0));
+    shoot(app, Vec2::new(200.0, 0.0));
+}
+
+#[test]
+fn every_player_gets_a_ball_on_the_tee() {
+    let mut app = headless_app();
+    start_hot_seat(&mut app, 3, 1);
+
+    assert_eq!(ball_positions(&mut app), vec![LEVEL_1_BALL; 3]);
+    assert_eq!(active_player(&mut app), Player(0));
+}
+
+#[test]
+fn the_ball_farthest_from_the_cup_plays_next() {
+    let mut app = headless_app();
+    start_hot_seat(&mut app, 2, 1);
+
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    assert_eq!(active_player(&mut app), Player(1));
+
+    // Still behind the first ball, so the second player goes again
+    shoot(&mut app, Vec2::new(50.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    assert_eq!(active_player(&mut app), Player(1));
+
+    let scoreboard = app.world.resource::<Scoreboard>();
+    assert_eq!(scoreboard.card(Player(0)).score, 1);
+    assert_eq!(scoreboard.card(Player(1)).score, 1);
+}
+
+#[test]
+fn the_next_hole_waits_for_every_ball_to_be_holed() {
+    let mut app = headless_app();
+    start_hot_seat(&mut app, 2, 1);
+
+    sink_active_ball(&mut app);
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    assert_eq!(app.world.resource::<Level>().0, 1);
+    assert_eq!(active_player(&mut app), Player(1));
+
+    sink_active_ball(&mut app);
+    step_until(&mut app, |app| game_state(app) == GameState::Flyover);
+    assert_eq!(app.world.resource::<Level>().0, 2);
+    let scoreboard = app.world.resource::<Scoreboard>();
+    assert_eq!(scoreboard.card(Player(0)).holes[0].strokes, 1);
+    assert_eq!(scoreboard.card(Player(1)).holes[0].strokes, 1);
+}
+
+#[test]
+fn a_hot_seat_round_ends_with_a_score_for_every_player() {
+    let mut app = headless_app();
+    start_hot_seat(&mut app, 2, LAST_LEVEL);
+    let mut game_over_reader = ManualEventReader::<GameOver>::default();
+
+    // The first player takes an extra stroke before sinking, the second one is
+    // farther from the cup by then and goes in first
+    shoot(&mut app, Vec2::new(0.0, 100.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    sink_active_ball(&mut app);
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    sink_active_ball(&mut app);
+    step_until(&mut app, |app| app_state(app) == AppState::GameOver);
+    app.update();
+
+    let events = app.world.resource::<Events<GameOver>>();
+    let game_over = game_over_reader.read(events).next().unwrap();
+    let scores: Vec<(usize, usize)> = game_over
+        .scores
+        .iter()
+        .map(|score| (score.player, score.score))
+        .collect();
+    assert_eq!(scores, vec![(0, 2), (1, 1)]);
+}
+
+#[test]
+fn a_hot_seat_replay_gives_every_shot_to_its_player() {
+    let mut app = headless_app();
+    start_hot_seat(&mut app, 2, 1);
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    shoot(&mut app, Vec2::new(450.0, -320.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    let recorded = ball_positions(&mut app);
+
+    watch_replay(&mut app);
+    step_until(&mut app, |app| {
+        app.world.resource::<Playback>().next_shot == 2 && game_state(app) == GameState::BallMoving
+    });
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+
+    assert_eq!(app.world.resource::<Players>().count, 2);
+    assert_eq!(ball_positions(&mut app), recorded);
+}
+
+fn place_player_ball(app: &mut App, player: Player, position: Vec2) {
+    let mut balls = app.world.query::<(&Player, &mut Transform)>();
+    for (ball_player, mut transform) in balls.iter_mut(&mut app.world) {
+        if *ball_player == player {
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+        }
+    }
+}
+
+#[test]
+fn a_mulligan_gives_the_turn_back_to_whoever_swung() {
+    let mut app = headless_app();
+    app.add_plugins((bevy::input::InputPlugin, ActionsPlugin, RetryPlugin))
+        .add_state::<PauseState>();
+    start_hot_seat(&mut app, 2, 1);
+    // Farther from the cup than the first player, who still has the tee shot
+    place_player_ball(&mut app, Player(1), LEVEL_1_BALL - Vec2::new(0.0, 20.0));
+    let before = ball_positions(&mut app);
+
+    // Taken back while the ball still rolls, so the turn is handed out again
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    let mut keys = app.world.resource_mut::<Input<KeyCode>>();
+    keys.press(KeyCode::U);
+    app.update();
+    app.world
+        .resource_mut::<Input<KeyCode>>()
+        .release(KeyCode::U);
+    app.update();
+
+    assert_eq!(ball_positions(&mut app), before);
+    assert_eq!(active_player(&mut app), Player(0));
+    let card = app.world.resource::<Scoreboard>().card(Player(0)).clone();
+    assert_eq!((card.score, card.holes[0].mulligans), (0, 1));
+}
+
+#[test]
+fn a_rolling_ball_knocks_a_resting_one_away() {
+    let mut app = headless_app();
+    start_hot_seat(&mut app, 2, 1);
+    let target = LEVEL_1_BALL + Vec2::new(100.0, 0.0);
+    place_player_ball(&mut app, Player(1), target);
+
+    shoot(&mut app, Vec2::new(400.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+
+    // Head on, so the first ball hands all its speed over and stays put
+    let positions = ball_positions(&mut app);
+    assert!(
+        positions[0].x < target.x && positions[0].x > target.x - 20.0,
+        "first ball stopped at {}",
+        positions[0]
+    );
+    assert!(
+        positions[1].x > target.x + 50.0,
+        "second ball stopped at {}",
+        positions[1]
+    );
+    assert_eq!(positions[1].y, target.y);
+}
+
+#[test]
+fn a_ball_knocked_into_the_cup_is_holed() {
+    let mut app = headless_app();
+    start_hot_seat(&mut app, 2, 1);
+    let hole = hole_position(&mut app);
+    place_player_ball(&mut app, Player(1), hole - Vec2::new(30.0, 0.0));
+    place_player_ball(&mut app, Player(0), hole - Vec2::new(80.0, 0.0));
+
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+
+    let holed: Vec<Player> = app
+        .world
+        .query_filtered::<&Player, With<Holed>>()
+        .iter(&app.world)
+        .copied()
+        .collect();
+    assert_eq!(holed, vec![Player(1)]);
+    assert_eq!(active_player(&mut app), Player(0));
+    assert_eq!(app.world.resource::<Level>().0, 1);
+}
+
+#[test]
+fn a_replay_knocks_the_balls_around_exactly_as_recorded() {
+    let mut app = headless_app();
+    start_hot_seat(&mut app, 2, 1);
+    place_player_ball(&mut app, Player(1), LEVEL_1_BALL + Vec2::new(100.0, 10.0));
+    shoot(&mut app, Vec2::new(450.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    let recorded = ball_positions(&mut app);
+
+    watch_replay(&mut app);
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+
+    assert_eq!(ball_positions(&mut app), recorded);
+}
+
+// The planner's own roll of its shot, checked against the real ball
+#[test]
+fn the_planned_shot_rolls_where_the_planner_said() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+    let walls = walls(&mut app);
+    let hole = hole_position(&mut app).extend(0.0);
+    let start = ball_position(&mut app).extend(2.0);
+
+    let planned = ai::plan_shot(&[start], 0, BALL_SIZE.truncate(), &walls, &[], hole);
+    assert!(planned.distance < 50.0, "{:?}", planned);
+
+    shoot(&mut app, planned.velocity);
+    step_until(&mut app, |app| {
+        game_state(app) != GameState::BallMoving || app.world.resource::<Level>().0 != 1
+    });
+    if planned.holed {
+        step_until(&mut app, |app| app.world.resource::<Level>().0 == 2);
+    } else {
+        assert_eq!(ball_position(&mut app), planned.rest);
+    }
+}
+
+#[test]
+fn a_missed_shot_is_the_same_every_time() {
+    let velocity = Vec2::new(400.0, 100.0);
+    let easy = ai::miss(velocity, Difficulty::Easy, 42);
+    assert_eq!(easy, ai::miss(velocity, Difficulty::Easy, 42));
+    assert_ne!(easy, velocity);
+
+    let hard = ai::miss(velocity, Difficulty::Hard, 42);
+    assert!(hard.distance(velocity) < easy.distance(velocity));
+}
+
+#[test]
+fn the_computer_takes_its_turn_by_itself() {
+    let mut app = headless_app();
+    app.world.resource_mut::<Players>().computer = Some(Difficulty::Hard);
+    start_hot_seat(&mut app, 2, 1);
+
+    // The person plays first, the computer waits for them
+    shoot(&mut app, Vec2::new(100.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+    assert_eq!(active_player(&mut app), Player(1));
+
+    step_until(&mut app, |app| game_state(app) == GameState::BallMoving);
+    let scoreboard = app.world.resource::<Scoreboard>();
+    assert_eq!(scoreboard.card(Player(1)).strokes(1), 1);
+}
+
+#[test]
+fn the_computer_plays_a_hole_alone() {
+    let mut app = headless_app();
+    app.world.resource_mut::<Players>().computer = Some(Difficulty::Hard);
+    start_round(&mut app, 1);
+
+    for _ in 0..10 {
+        if app.world.resource::<Level>().0 == 2 {
+            break;
+        }
+        step_until(&mut app, |app| {
+            game_state(app) == GameState::BallMoving || app.world.resource::<Level>().0 == 2
+        });
+        step_until(&mut app, |app| {
+            game_state(app) == GameState::DeadBall || app.world.resource::<Level>().0 == 2
+        });
+    }
+    assert_eq!(app.world.resource::<Level>().0, 2);
+}
+
+#[test]
+fn a_ball_in_the_water_goes_back_with_a_penalty_stroke() {
+    let mut app = headless_app();
+    start_round(&mut app, 1);
+    app.world.spawn((
+        Transform::from_xyz(-250.0, -100.0, 0.5).with_scale(Vec3::new(60.0, 60.0, 1.0)),
+        Water,
+    ));
+
+    shoot(&mut app, Vec2::new(300.0, 0.0));
+    step_until(&mut app, |app| game_state(app) == GameState::DeadBall);
+
+    assert_eq!(ball_position(&mut app), LEVEL_1_BALL);
+    let card = app.world.resource::<Scoreboard>().card(Player(0));
+    assert_eq!(card.score, 2);
+    assert_eq!(card.holes[0].strokes, 2);
+}
+
+// Waits for the generated hole of `level` instead of a built-in one
+fn start_endless_round(app: &mut App, seed: u64, level: i32) {
+    *app.world.resource_mut::<Course>() = Course::Endless { seed };
+    app.world.resource_mut::<Level>().0 = level;
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Game);
+    wait_until(app, |app| game_state(app) == GameState::Flyover);
+    skip_flyover(app);
+}
+
+#[test]
+fn an_endless_course_keeps_loading_holes() {
+    let mut app = headless_app();
+    start_endless_round(&mut app, 7, LAST_LEVEL);
+    assert!(app.world.resource::<HolePar>().0.is_some());
+
+    let hole = hole_position(&mut app);
+    place_ball(&mut app, hole);
+    shoot(&mut app, Vec2::new(10.0, 0.0));
+    wait_until(&mut app, |app| game_state(app) == GameState::Flyover);
+
+    assert_eq!(app.world.resource::<Level>().0, LAST_LEVEL + 1);
+    assert_eq!(app_state(&app), AppState::Game);
+    assert_eq!(app.world.resource::<Scoreboard>().card(Player(0)).score, 1);
+}
+
+#[test]
+fn a_restarted_endless_hole_is_not_built_again() {
+    let mut app = headless_app();
+    start_endless_round(&mut app, 11, 1);
+    assert!(app.world.resource::<GeneratedHoles>().get(11, 1).is_some());
+    let walls_before = walls(&mut app);
+
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::RestartingHole);
+    // Straight from the cache, a handful of updates instead of a wait
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(game_state(&app), GameState::Flyover);
+    let walls_after = walls(&mut app);
+    assert_eq!(walls_before.len(), walls_after.len());
+    for (before, after) in walls_before.iter().zip(&walls_after) {
+        assert_eq!((before.center, before.size), (after.center, after.size));
+    }
+}
+
+// The minimap and what it needs from the windowed game
+fn add_minimap(app: &mut App) {
+    app.add_plugins((bevy::input::InputPlugin, ActionsPlugin, MinimapPlugin))
+        .add_state::<PauseState>()
+        .init_resource::<CameraRig>()
+        .init_resource::<UiScale>()
+        .insert_resource(Settings::default());
+}
+
+#[test]
+fn the_minimap_marks_every_ball_and_the_one_to_play() {
+    let mut app = headless_app();
+    add_minimap(&mut app);
+    // The last built-in hole is too big for the screen, so it has a minimap
+    start_hot_seat(&mut app, 3, LAST_LEVEL);
+    app.update();
+
+    let active = app
+        .world
+        .query_filtered::<Entity, With<ActiveBall>>()
+        .single(&app.world);
+    let markers: Vec<(Entity, Val)> = app
+        .world
+        .query::<(&MinimapBall, &Style)>()
+        .iter(&app.world)
+        .map(|(marker, style)| (marker.ball, style.width))
+        .collect();
+    assert_eq!(markers.len(), 3);
+    let active_width = markers.iter().find(|(ball, _)| *ball == active).unwrap().1;
+    for (ball, width) in &markers {
+        if *ball != active {
+            assert!(
+                matches!((width, active_width), (Val::Px(width), Val::Px(active)) if *width < active)
+            );
+        }
+    }
+}
+
+#[test]
+fn the_minimap_shows_the_ponds() {
+    let mut app = headless_app();
+    add_minimap(&mut app);
+    app.world.resource_mut::<Level>().0 = LAST_LEVEL;
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Game);
+    // The built-in holes are dry, so one gets a pond before the map is drawn
+    step_until(&mut app, |app| game_state(app) == GameState::LoadingMap);
+    app.world.spawn((
+        Transform {
+            translation: Vec3::new(0.0, 0.0, 0.5),
+            scale: Vec3::new(200.0, 100.0, 1.0),
+            ..default()
+        },
+        Water,
+    ));
+    skip_flyover(&mut app);
+
+    let water = Settings::default().palette().water;
+    let children = app
+        .world
+        .query_filtered::<&Children, With<Minimap>>()
+        .single(&app.world)
+        .to_vec();
+    let ponds = children
+        .iter()
+        .filter(|child| {
+            app.world
+                .get::<BackgroundColor>(**child)
+                .map(|color| color.0)
+                == Some(water)
+        })
+        .count();
+    assert_eq!(ponds, 1);
+}
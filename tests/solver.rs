@@ -0,0 +1,191 @@
+// Solvability checks of `par` on level files and small hand made layouts, and the
+// holes the generator builds from a seed.
+
+use std::fs;
+
+use bevy::prelude::*;
+
+use golf_2d_bevy::game::ai::solver::{self, MAX_STROKES};
+use golf_2d_bevy::game::ai::try_shot;
+use golf_2d_bevy::game::levels::generator;
+use golf_2d_bevy::game::levels::layout::{BoxLayout, LevelLayout};
+use golf_2d_bevy::game::physics;
+use golf_2d_bevy::BALL_SIZE;
+
+fn wall(center: Vec2, size: Vec2) -> BoxLayout {
+    BoxLayout { center, size }
+}
+
+// Four walls around a box of `size`, centered on the origin
+fn boundary(size: Vec2) -> Vec<BoxLayout> {
+    let half = size / 2.0;
+    vec![
+        wall(Vec2::new(-half.x, 0.0), Vec2::new(30.0, size.y + 30.0)),
+        wall(Vec2::new(half.x, 0.0), Vec2::new(30.0, size.y + 30.0)),
+        wall(Vec2::new(0.0, -half.y), Vec2::new(size.x + 30.0, 30.0)),
+        wall(Vec2::new(0.0, half.y), Vec2::new(size.x + 30.0, 30.0)),
+    ]
+}
+
+#[test]
+fn the_example_level_file_is_solvable() {
+    let text = fs::read_to_string("assets/levels/example.ron").unwrap();
+    let layout = LevelLayout::from_ron(&text).unwrap();
+    assert_eq!(layout.walls.len(), 5);
+
+    let solution = solver::solve(&layout);
+    let strokes = solution.strokes().unwrap();
+    assert!(strokes <= MAX_STROKES);
+    assert_eq!(solution.par(), Some(strokes + 1));
+}
+
+#[test]
+fn a_straight_putt_is_a_hole_in_one() {
+    let layout = LevelLayout {
+        ball: Vec2::new(-200.0, 0.0),
+        hole: Vec2::new(200.0, 0.0),
+        walls: boundary(Vec2::new(600.0, 200.0)),
+        water: Vec::new(),
+    };
+    let solution = solver::solve(&layout);
+    assert_eq!(solution.strokes(), Some(1));
+    assert_eq!(solution.par(), Some(2));
+}
+
+#[test]
+fn a_walled_in_tee_is_not_solvable() {
+    let mut walls = boundary(Vec2::new(600.0, 200.0));
+    walls.extend(boundary(Vec2::new(60.0, 60.0)));
+    let layout = LevelLayout {
+        ball: Vec2::ZERO,
+        hole: Vec2::new(200.0, 0.0),
+        walls,
+        water: Vec::new(),
+    };
+    let solution = solver::solve(&layout);
+    assert_eq!(solution.strokes(), None);
+    assert!(solution
+        .rests
+        .iter()
+        .all(|rest| rest.x.abs() < 30.0 && rest.y.abs() < 30.0));
+}
+
+#[test]
+fn the_heatmap_shows_walls_ponds_tee_cup_and_rests() {
+    let layout = LevelLayout {
+        ball: Vec2::new(-200.0, 0.0),
+        hole: Vec2::new(200.0, 0.0),
+        walls: boundary(Vec2::new(600.0, 200.0)),
+        water: vec![wall(Vec2::new(0.0, 60.0), Vec2::new(60.0, 60.0))],
+    };
+    let map = solver::heatmap(&layout, &[Vec2::new(0.0, 0.0)], 40);
+    let rows: Vec<&str> = map.lines().collect();
+    assert!(rows.iter().all(|row| row.chars().count() == 40));
+    assert!(rows.first().unwrap().chars().all(|glyph| glyph == '#'));
+    assert_eq!(map.matches('T').count(), 1);
+    assert_eq!(map.matches('O').count(), 1);
+    assert_eq!(map.matches('@').count(), 1);
+    assert!(map.contains('~'));
+}
+
+#[test]
+fn a_shot_into_a_pond_comes_back_to_where_it_was_taken() {
+    let start = Vec3::new(-200.0, 0.0, 0.0);
+    let pond = Rect::from_center_size(Vec2::ZERO, Vec2::splat(60.0));
+    let shot = try_shot(
+        &[start],
+        0,
+        BALL_SIZE.truncate(),
+        Vec2::new(400.0, 0.0),
+        &[],
+        &[pond],
+        Vec3::new(200.0, 0.0, 0.0),
+    );
+    assert_eq!(shot.rest, start.truncate());
+    assert!(!shot.holed);
+    assert!(shot.in_water);
+    assert_eq!(shot.distance, f32::INFINITY);
+}
+
+#[test]
+fn shots_into_a_pond_are_not_counted_as_resting() {
+    let layout = LevelLayout {
+        ball: Vec2::new(-200.0, 0.0),
+        hole: Vec2::new(200.0, 0.0),
+        walls: boundary(Vec2::new(600.0, 200.0)),
+        water: vec![wall(Vec2::new(-100.0, 0.0), Vec2::new(60.0, 170.0))],
+    };
+    let solution = solver::solve(&layout);
+    assert!(solution.splashed > 0);
+    assert!(solution.rests.iter().all(|rest| *rest != layout.ball));
+    assert!(solution.rests.len() + solution.splashed <= solution.tried);
+}
+
+#[test]
+fn a_ball_in_the_way_takes_the_shot() {
+    let start = Vec3::new(-200.0, 0.0, 0.0);
+    let hole = Vec3::new(200.0, 0.0, 0.0);
+    let velocity = Vec2::new(400.0, 0.0);
+    let size = BALL_SIZE.truncate();
+    assert!(try_shot(&[start], 0, size, velocity, &[], &[], hole).holed);
+
+    // Head on, so the ball in the way rolls on and the shot stops where they met
+    let shot = try_shot(&[start, Vec3::ZERO], 0, size, velocity, &[], &[], hole);
+    assert!(!shot.holed);
+    assert!(shot.rest.x < 0.0, "{:?}", shot.rest);
+}
+
+#[test]
+fn a_ball_knocked_in_stays_in_the_cup() {
+    let size = BALL_SIZE.truncate();
+    let hole = Vec3::new(200.0, 0.0, 0.0);
+    // Right behind the cup, a ball rolling over it would come back
+    let walls = [physics::Wall {
+        center: Vec3::new(240.0, 0.0, 0.0),
+        size: Vec2::new(30.0, 200.0),
+    }];
+    let target = Vec3::new(150.0, 0.0, 0.0);
+
+    // The shooter is the second player, the first one's ball is the one knocked in
+    let shot = try_shot(
+        &[target, Vec3::new(-200.0, 0.0, 0.0)],
+        1,
+        size,
+        Vec2::new(700.0, 0.0),
+        &walls,
+        &[],
+        hole,
+    );
+    assert!(!shot.holed);
+    assert!(shot.rest.x > 120.0, "{:?}", shot.rest);
+}
+
+#[test]
+fn the_fallback_hole_takes_the_par_it_claims() {
+    let hole = generator::fallback_hole();
+    assert_eq!(solver::solve(&hole.layout).par(), Some(hole.par));
+}
+
+#[test]
+fn the_same_seed_builds_the_same_hole() {
+    let first = generator::generate(3);
+    let second = generator::generate(3);
+    assert_eq!(first.layout, second.layout);
+    assert_eq!(first.par, second.par);
+    assert_ne!(first.layout, generator::generate(4).layout);
+}
+
+#[test]
+fn generated_holes_are_solvable_with_a_dry_tee_and_cup() {
+    for seed in 0..3 {
+        let hole = generator::generate(seed);
+        let layout = &hole.layout;
+        assert_eq!(solver::solve(layout).par(), Some(hole.par), "seed {}", seed);
+        assert!(hole.par <= MAX_STROKES + 1, "seed {}", seed);
+        for area in layout.walls.iter().chain(&layout.water) {
+            let area = Rect::from_center_size(area.center, area.size);
+            assert!(!area.contains(layout.ball), "seed {}", seed);
+            assert!(!area.contains(layout.hole), "seed {}", seed);
+        }
+    }
+}